@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// A single historical buy made by a candidate wallet, replayed during a
+/// backtest to decide whether the wallet is worth mirroring.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalTrade {
+    pub wallet_address: String,
+    pub token_address: String,
+    pub entry_timestamp: u64,
+    pub entry_price: f64,
+    pub amount_eth: f64,
+}
+
+/// Per-wallet outcome of replaying its trades against realised prices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBacktest {
+    pub address: String,
+    pub trades: u32,
+    pub wins: u32,
+    pub win_rate: f64,
+    pub avg_multiplier: f64,
+    pub total_return_eth: f64,
+}
+
+/// Replays historical alpha-wallet buys against price data to score which
+/// wallets to mirror. Prices are pulled from Dexscreener, matching
+/// [`crate::alpha_tracker`].
+pub struct Backtester {
+    client: Client,
+    dexscreener_base: String,
+    hold_seconds: u64,
+}
+
+impl Backtester {
+    pub fn new(hold_seconds: u64) -> Self {
+        Self {
+            client: Client::new(),
+            dexscreener_base: "https://api.dexscreener.com/latest".to_string(),
+            hold_seconds,
+        }
+    }
+
+    /// Score every wallet that appears in `trades` by replaying each buy and
+    /// measuring its multiplier against the exit price [`price_after`](Self::price_after)
+    /// returns for the end of the configured hold window.
+    pub async fn evaluate(&self, trades: &[HistoricalTrade]) -> Result<Vec<WalletBacktest>> {
+        let mut by_wallet: HashMap<String, WalletBacktest> = HashMap::new();
+
+        for trade in trades {
+            let exit_price = self
+                .price_after(&trade.token_address, trade.entry_timestamp + self.hold_seconds)
+                .await
+                .unwrap_or(trade.entry_price);
+
+            let multiplier = if trade.entry_price > 0.0 {
+                exit_price / trade.entry_price
+            } else {
+                0.0
+            };
+            let pnl = trade.amount_eth * (multiplier - 1.0);
+
+            let entry = by_wallet
+                .entry(trade.wallet_address.clone())
+                .or_insert_with(|| WalletBacktest {
+                    address: trade.wallet_address.clone(),
+                    trades: 0,
+                    wins: 0,
+                    win_rate: 0.0,
+                    avg_multiplier: 0.0,
+                    total_return_eth: 0.0,
+                });
+
+            entry.avg_multiplier =
+                (entry.avg_multiplier * entry.trades as f64 + multiplier) / (entry.trades as f64 + 1.0);
+            entry.trades += 1;
+            if multiplier > 1.0 {
+                entry.wins += 1;
+            }
+            entry.total_return_eth += pnl;
+        }
+
+        let mut results: Vec<WalletBacktest> = by_wallet.into_values().collect();
+        for result in &mut results {
+            result.win_rate = result.wins as f64 / result.trades as f64;
+        }
+        results.sort_by(|a, b| b.total_return_eth.partial_cmp(&a.total_return_eth).unwrap());
+        Ok(results)
+    }
+
+    /// Select the addresses worth mirroring from a scored backtest.
+    pub fn select(&self, results: &[WalletBacktest], min_win_rate: f64, min_multiplier: f64) -> Vec<String> {
+        results
+            .iter()
+            .filter(|r| r.win_rate >= min_win_rate && r.avg_multiplier >= min_multiplier)
+            .map(|r| r.address.clone())
+            .collect()
+    }
+
+    /// Fetch the token's current price from Dexscreener. The timestamp is not
+    /// used: no historical price is looked up, so every replayed trade exits at
+    /// the live price. Returns `0.0` when no pair lists a price.
+    async fn price_after(&self, token_address: &str, _timestamp: u64) -> Result<f64> {
+        let url = format!("{}/dex/tokens/{}", self.dexscreener_base, token_address);
+        let response: serde_json::Value = self.client.get(&url).send().await?.json().await?;
+
+        let price = response["pairs"]
+            .as_array()
+            .and_then(|pairs| pairs.first())
+            .and_then(|pair| pair["priceUsd"].as_str())
+            .and_then(|p| p.parse::<f64>().ok())
+            .unwrap_or(0.0);
+
+        Ok(price)
+    }
+}
@@ -0,0 +1,399 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Behaviour the control server exposes to operators. Implemented by the engine
+/// so the transport stays decoupled from the trading logic.
+#[async_trait]
+pub trait ControlHandler: Send + Sync + 'static {
+    /// Snapshot of engine state: capital, tracked wallets, open positions.
+    async fn status(&self) -> Value;
+    /// Pause or resume mirroring. Returns the resulting paused state.
+    async fn set_paused(&self, paused: bool) -> bool;
+    /// Live trading metrics (PnL, win rate, drawdown).
+    async fn metrics(&self) -> Value;
+    /// Operator command: close every open position immediately. Returns the
+    /// number of positions that were closed.
+    async fn close_all(&self) -> usize;
+    /// Every tracked alpha wallet and its stats.
+    async fn list_wallets(&self) -> Vec<Value>;
+    /// Start tracking a new alpha wallet at runtime. Returns `false` if an
+    /// entry for the same address already exists.
+    async fn add_wallet(&self, wallet: Value) -> bool;
+    /// Stop tracking an alpha wallet at runtime. Returns `false` if it wasn't
+    /// tracked.
+    async fn remove_wallet(&self, address: &str) -> bool;
+    /// Current capital and every open position.
+    async fn capital(&self) -> Value;
+    /// The most recent `limit` trade signals, newest first.
+    async fn recent_signals(&self, limit: usize) -> Vec<Value>;
+    /// Manually inject a trade signal as if it had been mirrored from an
+    /// alpha wallet. Returns `false` if the signal was malformed.
+    async fn inject_signal(&self, signal: Value) -> bool;
+    /// Veto a token address so any matching signal is dropped instead of
+    /// acted on.
+    async fn veto_signal(&self, token_address: &str) -> bool;
+    /// Dump the transaction hashes currently tracked as pending.
+    async fn pending_hashes(&self) -> Vec<String>;
+    /// Order status for every tracked pending hash, as reported by the
+    /// implementation's order backend (`OkxClient::get_order_status` where
+    /// one is held). `AlphaMirror` holds no `OkxClient` and returns an empty
+    /// list.
+    async fn open_orders(&self) -> Vec<Value>;
+    /// Live-tune the fraction of capital a mirrored signal is sized against.
+    /// Returns the previous value. `MempoolScanner`'s trade executor sizes its
+    /// buys by fractional Kelly and does not read this value.
+    async fn set_position_size(&self, pct: f64) -> f64;
+    /// Live-tune the minimum win rate a wallet needs before its signals are
+    /// mirrored. Returns the previous value. `MempoolScanner`'s trade executor
+    /// keeps its own fixed `win_rate > 0.7` gate and does not read this value.
+    async fn set_win_rate_threshold(&self, threshold: f64) -> f64;
+}
+
+/// Newline-delimited JSON-RPC 2.0 control server for live introspection and
+/// manual overrides. One request object per line, one response per line.
+pub struct ControlServer<H: ControlHandler> {
+    handler: Arc<H>,
+    /// Shared secret every request must echo back in its top-level `auth`
+    /// field. `None` leaves the server open, matching the server's original
+    /// unauthenticated behaviour.
+    auth_token: Option<String>,
+}
+
+impl<H: ControlHandler> ControlServer<H> {
+    pub fn new(handler: Arc<H>) -> Self {
+        Self { handler, auth_token: None }
+    }
+
+    /// Require every request to carry a matching bearer token, gating
+    /// operator commands behind a shared secret.
+    pub fn with_auth_token(mut self, auth_token: String) -> Self {
+        self.auth_token = Some(auth_token);
+        self
+    }
+
+    /// Bind `addr` (e.g. `127.0.0.1:8645`) and serve connections until the
+    /// listener errors.
+    pub async fn serve(&self, addr: &str) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        println!("🛂 Control server listening on {}", addr);
+
+        loop {
+            let (socket, peer) = listener.accept().await?;
+            let handler = self.handler.clone();
+            let auth_token = self.auth_token.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(socket, handler, auth_token).await {
+                    println!("⚠️ Control connection {} closed: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+async fn handle_connection<H: ControlHandler>(
+    socket: TcpStream,
+    handler: Arc<H>,
+    auth_token: Option<String>,
+) -> Result<()> {
+    let (read_half, mut write_half) = socket.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = dispatch(&handler, auth_token.as_deref(), &line).await;
+        let mut bytes = serde_json::to_vec(&response)?;
+        bytes.push(b'\n');
+        write_half.write_all(&bytes).await?;
+    }
+    Ok(())
+}
+
+async fn dispatch<H: ControlHandler>(handler: &Arc<H>, auth_token: Option<&str>, line: &str) -> Value {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(req) => req,
+        Err(_) => return error_response(Value::Null, -32700, "Parse error"),
+    };
+
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+
+    if let Some(expected) = auth_token {
+        let provided = request.get("auth").and_then(Value::as_str);
+        if provided != Some(expected) {
+            return error_response(id, -32001, "Unauthorized");
+        }
+    }
+
+    let method = request.get("method").and_then(Value::as_str).unwrap_or_default();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    match method {
+        "status" => ok_response(id, handler.status().await),
+        "pause" => ok_response(id, json!({ "paused": handler.set_paused(true).await })),
+        "resume" => ok_response(id, json!({ "paused": handler.set_paused(false).await })),
+        "set_paused" => {
+            let paused = params.get("paused").and_then(Value::as_bool).unwrap_or(true);
+            ok_response(id, json!({ "paused": handler.set_paused(paused).await }))
+        }
+        "metrics" => ok_response(id, handler.metrics().await),
+        "close_all" => ok_response(id, json!({ "closed": handler.close_all().await })),
+        "list_wallets" => ok_response(id, json!(handler.list_wallets().await)),
+        "add_wallet" => {
+            let wallet = params.get("wallet").cloned().unwrap_or(Value::Null);
+            ok_response(id, json!({ "added": handler.add_wallet(wallet).await }))
+        }
+        "remove_wallet" => {
+            let address = params.get("address").and_then(Value::as_str).unwrap_or_default();
+            ok_response(id, json!({ "removed": handler.remove_wallet(address).await }))
+        }
+        "capital" => ok_response(id, handler.capital().await),
+        "signals" => {
+            let limit = params.get("limit").and_then(Value::as_u64).unwrap_or(20) as usize;
+            ok_response(id, json!(handler.recent_signals(limit).await))
+        }
+        "inject_signal" => {
+            let signal = params.get("signal").cloned().unwrap_or(Value::Null);
+            ok_response(id, json!({ "injected": handler.inject_signal(signal).await }))
+        }
+        "veto_signal" => {
+            let address = params.get("token_address").and_then(Value::as_str).unwrap_or_default();
+            ok_response(id, json!({ "vetoed": handler.veto_signal(address).await }))
+        }
+        // Alias added alongside get_pending/get_open_orders below: same data
+        // as list_wallets, named to match this request's wording.
+        "list_alpha_wallets" => ok_response(id, json!(handler.list_wallets().await)),
+        "get_pending" => ok_response(id, json!(handler.pending_hashes().await)),
+        "get_open_orders" => ok_response(id, json!(handler.open_orders().await)),
+        "set_position_size" => {
+            let pct = params.get("position_size_pct").and_then(Value::as_f64).unwrap_or(0.3);
+            ok_response(id, json!({ "previous": handler.set_position_size(pct).await }))
+        }
+        "set_win_rate_threshold" => {
+            let threshold = params.get("win_rate_threshold").and_then(Value::as_f64).unwrap_or(0.7);
+            ok_response(id, json!({ "previous": handler.set_win_rate_threshold(threshold).await }))
+        }
+        _ => error_response(id, -32601, "Method not found"),
+    }
+}
+
+fn ok_response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use tokio::io::AsyncWriteExt;
+
+    /// In-memory stand-in for the engine, exercised end-to-end over the real
+    /// TCP transport rather than by calling the trait directly.
+    struct TestHandler {
+        wallets: Mutex<Vec<Value>>,
+        signals: Mutex<Vec<Value>>,
+        vetoed: Mutex<Vec<String>>,
+        pending: Mutex<Vec<String>>,
+        position_size_pct: Mutex<f64>,
+        win_rate_threshold: Mutex<f64>,
+    }
+
+    #[async_trait]
+    impl ControlHandler for TestHandler {
+        async fn status(&self) -> Value {
+            json!({ "ok": true })
+        }
+        async fn set_paused(&self, paused: bool) -> bool {
+            paused
+        }
+        async fn metrics(&self) -> Value {
+            json!({})
+        }
+        async fn close_all(&self) -> usize {
+            0
+        }
+        async fn list_wallets(&self) -> Vec<Value> {
+            self.wallets.lock().clone()
+        }
+        async fn add_wallet(&self, wallet: Value) -> bool {
+            let address = wallet.get("address").and_then(Value::as_str).map(str::to_string);
+            let mut wallets = self.wallets.lock();
+            if let Some(address) = &address {
+                if wallets.iter().any(|w| w.get("address").and_then(Value::as_str) == Some(address)) {
+                    return false;
+                }
+            }
+            wallets.push(wallet);
+            true
+        }
+        async fn remove_wallet(&self, address: &str) -> bool {
+            let mut wallets = self.wallets.lock();
+            let before = wallets.len();
+            wallets.retain(|w| w.get("address").and_then(Value::as_str) != Some(address));
+            wallets.len() != before
+        }
+        async fn capital(&self) -> Value {
+            json!({ "current_capital": "1000.0", "open_positions": [] })
+        }
+        async fn recent_signals(&self, limit: usize) -> Vec<Value> {
+            let signals = self.signals.lock();
+            signals.iter().rev().take(limit).cloned().collect()
+        }
+        async fn inject_signal(&self, signal: Value) -> bool {
+            if signal.is_null() {
+                return false;
+            }
+            self.signals.lock().push(signal);
+            true
+        }
+        async fn veto_signal(&self, token_address: &str) -> bool {
+            self.vetoed.lock().push(token_address.to_string());
+            true
+        }
+        async fn pending_hashes(&self) -> Vec<String> {
+            self.pending.lock().clone()
+        }
+        async fn open_orders(&self) -> Vec<Value> {
+            self.pending
+                .lock()
+                .iter()
+                .map(|hash| json!({ "txHash": hash, "status": "filled" }))
+                .collect()
+        }
+        async fn set_position_size(&self, pct: f64) -> f64 {
+            std::mem::replace(&mut *self.position_size_pct.lock(), pct)
+        }
+        async fn set_win_rate_threshold(&self, threshold: f64) -> f64 {
+            std::mem::replace(&mut *self.win_rate_threshold.lock(), threshold)
+        }
+    }
+
+    async fn send_line(stream: &mut TcpStream, request: Value) -> Value {
+        let mut bytes = request.to_string().into_bytes();
+        bytes.push(b'\n');
+        stream.write_all(&bytes).await.unwrap();
+
+        let (read_half, _) = stream.split();
+        let mut lines = BufReader::new(read_half).lines();
+        let line = lines.next_line().await.unwrap().unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn drives_wallet_capital_and_signal_methods_end_to_end() {
+        let handler = Arc::new(TestHandler {
+            wallets: Mutex::new(Vec::new()),
+            signals: Mutex::new(Vec::new()),
+            vetoed: Mutex::new(Vec::new()),
+            pending: Mutex::new(Vec::new()),
+            position_size_pct: Mutex::new(0.3),
+            win_rate_threshold: Mutex::new(0.7),
+        });
+        let server = ControlServer::new(handler);
+        let addr = "127.0.0.1:18645";
+        tokio::spawn(async move {
+            let _ = server.serve(addr).await;
+        });
+        // Give the listener a moment to bind before connecting.
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+
+        let added = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "add_wallet", "params": {"wallet": {"address": "0xabc"}}}),
+        )
+        .await;
+        assert_eq!(added["result"]["added"], json!(true));
+
+        let wallets = send_line(&mut stream, json!({"jsonrpc": "2.0", "id": 2, "method": "list_wallets"})).await;
+        assert_eq!(wallets["result"].as_array().unwrap().len(), 1);
+
+        let capital = send_line(&mut stream, json!({"jsonrpc": "2.0", "id": 3, "method": "capital"})).await;
+        assert_eq!(capital["result"]["current_capital"], json!("1000.0"));
+
+        let injected = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 4, "method": "inject_signal", "params": {"signal": {"token_address": "0xdead"}}}),
+        )
+        .await;
+        assert_eq!(injected["result"]["injected"], json!(true));
+
+        let signals = send_line(&mut stream, json!({"jsonrpc": "2.0", "id": 5, "method": "signals", "params": {"limit": 10}})).await;
+        assert_eq!(signals["result"].as_array().unwrap().len(), 1);
+
+        let vetoed = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 6, "method": "veto_signal", "params": {"token_address": "0xdead"}}),
+        )
+        .await;
+        assert_eq!(vetoed["result"]["vetoed"], json!(true));
+
+        let removed = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 7, "method": "remove_wallet", "params": {"address": "0xabc"}}),
+        )
+        .await;
+        assert_eq!(removed["result"]["removed"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn rejects_unauthenticated_requests_and_drives_operator_tunables() {
+        let handler = Arc::new(TestHandler {
+            wallets: Mutex::new(Vec::new()),
+            signals: Mutex::new(Vec::new()),
+            vetoed: Mutex::new(Vec::new()),
+            pending: Mutex::new(vec!["0xhash1".to_string()]),
+            position_size_pct: Mutex::new(0.3),
+            win_rate_threshold: Mutex::new(0.7),
+        });
+        let server = ControlServer::new(handler).with_auth_token("s3cret".to_string());
+        let addr = "127.0.0.1:18646";
+        tokio::spawn(async move {
+            let _ = server.serve(addr).await;
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+
+        let unauthorized = send_line(&mut stream, json!({"jsonrpc": "2.0", "id": 1, "method": "get_pending"})).await;
+        assert_eq!(unauthorized["error"]["code"], json!(-32001));
+
+        let pending = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "get_pending", "auth": "s3cret"}),
+        )
+        .await;
+        assert_eq!(pending["result"], json!(["0xhash1"]));
+
+        let orders = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "get_open_orders", "auth": "s3cret"}),
+        )
+        .await;
+        assert_eq!(orders["result"][0]["txHash"], json!("0xhash1"));
+
+        let resized = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 4, "method": "set_position_size", "auth": "s3cret", "params": {"position_size_pct": 0.5}}),
+        )
+        .await;
+        assert_eq!(resized["result"]["previous"], json!(0.3));
+
+        let rethresholded = send_line(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 5, "method": "set_win_rate_threshold", "auth": "s3cret", "params": {"win_rate_threshold": 0.8}}),
+        )
+        .await;
+        assert_eq!(rethresholded["result"]["previous"], json!(0.7));
+    }
+}
@@ -0,0 +1,767 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use futures_util::stream::StreamExt;
+use parking_lot::Mutex;
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Amount;
+use crate::okx_dex_api::{OkxClient, TradeParams, ExecutionResult};
+
+/// Executes a mirrored buy through whichever backend is configured: the
+/// custodial `OkxClient` swap endpoint, or a self-custody
+/// `WalletConnectSigner` that has the operator's own wallet sign and
+/// broadcast. `ExecutionEngine` sources liquidity/price quotes from
+/// `OkxClient` either way -- only the signing/submission step is swappable.
+#[async_trait]
+pub trait TradeSigner: Send + Sync {
+    async fn execute_buy_order(&self, params: TradeParams) -> Result<ExecutionResult>;
+}
+
+#[async_trait]
+impl TradeSigner for OkxClient {
+    async fn execute_buy_order(&self, params: TradeParams) -> Result<ExecutionResult> {
+        OkxClient::execute_buy_order(self, params).await
+    }
+}
+
+/// Prices are tracked as integer-scaled fixed-point values (1e18 scale) so that
+/// value math stays in `U256` space; see [`scale_price`].
+const PRICE_SCALE: u64 = 18;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub token_address: String,
+    /// Entry price scaled by 1e18 (wei of ETH per whole token).
+    #[serde(with = "crate::money::hex_or_decimal_u256")]
+    pub entry_price: U256,
+    pub amount: Amount,
+    pub timestamp: u64,
+    #[serde(with = "crate::money::hex_or_decimal_u256")]
+    pub stop_loss: U256,
+    #[serde(with = "crate::money::hex_or_decimal_u256")]
+    pub take_profit: U256,
+    pub current_value: Amount,
+    /// Signed PnL in wei; stored as its own amount plus a sign flag so the
+    /// unsigned `U256` type can represent losses.
+    pub unrealized_pnl: Amount,
+    pub unrealized_loss: bool,
+    /// Highest price seen since entry (1e18-scaled), used to ratchet the
+    /// trailing stop.
+    #[serde(with = "crate::money::hex_or_decimal_u256")]
+    pub peak_price: U256,
+    /// Trailing-stop distance below `peak_price`, as a percent (e.g. `20` keeps
+    /// the stop 20% under the peak). The stop only ever rises.
+    pub trailing_stop_pct: u64,
+    /// Take-profit ladder: sell a fraction of the position each time a rung's
+    /// multiple of entry is reached. The remainder rides the trailing stop.
+    pub ladder: Vec<TakeProfitRung>,
+    /// Realized PnL banked from partial sells so far (ETH, signed).
+    pub realized_pnl: f64,
+}
+
+/// One rung of a take-profit ladder: once the price reaches `trigger_mult_x100`
+/// percent of entry, sell `fraction_pct` percent of the remaining position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeProfitRung {
+    pub trigger_mult_x100: u64,
+    pub fraction_pct: u8,
+    pub filled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeMetrics {
+    pub total_trades: u32,
+    pub winning_trades: u32,
+    pub total_pnl: f64,
+    pub max_drawdown: f64,
+    pub win_rate: f64,
+    pub avg_trade_duration: f64,
+    /// PnL banked from closed legs/positions, tracked separately from the
+    /// open-position mark-to-market below.
+    pub realized_pnl: f64,
+    /// Aggregate unrealized PnL across open positions, refreshed each sync.
+    pub unrealized_pnl: f64,
+}
+
+/// Versioned on-disk form of the engine state, sealed by [`ExecutionEngine::backup`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EngineSnapshot {
+    version: u16,
+    positions: HashMap<String, Position>,
+    capital: Amount,
+    metrics: TradeMetrics,
+}
+
+pub struct ExecutionEngine {
+    okx_client: Arc<OkxClient>,
+    /// Backend that actually signs and submits a mirrored buy. Defaults to
+    /// `okx_client` itself; swap it out with `with_signer` (e.g. for a
+    /// `WalletConnectSigner`) to route trades through self-custody instead.
+    signer: Arc<dyn TradeSigner>,
+    positions: Arc<RwLock<HashMap<String, Position>>>,
+    capital: Arc<Mutex<Amount>>,
+    metrics: Arc<Mutex<TradeMetrics>>,
+    /// Dynamic risk layer: derives slippage, stop/take bands, and sizing from
+    /// live market state instead of fixed constants.
+    risk_config: crate::risk_config::RiskConfig,
+    /// Handle to the live WebSocket price subscription, when one is attached, so
+    /// opening a position starts streaming its price and closing one stops.
+    price_feed: Mutex<Option<crate::price_stream::PriceSubscription>>,
+    /// Resting limit orders matched against live prices by the sync loop and
+    /// price stream.
+    order_book: Mutex<crate::order_book::OrderBook>,
+}
+
+impl ExecutionEngine {
+    pub fn new(okx_client: Arc<OkxClient>) -> Self {
+        Self {
+            signer: okx_client.clone() as Arc<dyn TradeSigner>,
+            okx_client,
+            positions: Arc::new(RwLock::new(HashMap::new())),
+            capital: Arc::new(Mutex::new(Amount::ether_from_f64(1000.0))),
+            metrics: Arc::new(Mutex::new(TradeMetrics {
+                total_trades: 0,
+                winning_trades: 0,
+                total_pnl: 0.0,
+                max_drawdown: 0.0,
+                win_rate: 0.0,
+                avg_trade_duration: 0.0,
+                realized_pnl: 0.0,
+                unrealized_pnl: 0.0,
+            })),
+            risk_config: crate::risk_config::RiskConfig::default(),
+            price_feed: Mutex::new(None),
+            order_book: Mutex::new(crate::order_book::OrderBook::new()),
+        }
+    }
+
+    /// Override the default risk layer (e.g. tighter ceilings or per-token pins).
+    pub fn with_risk_config(mut self, risk_config: crate::risk_config::RiskConfig) -> Self {
+        self.risk_config = risk_config;
+        self
+    }
+
+    /// Route mirrored buys through a different execution backend -- e.g. a
+    /// self-custody `WalletConnectSigner` -- instead of the default custodial
+    /// `OkxClient` path. Liquidity/price lookups keep using `okx_client`
+    /// either way.
+    pub fn with_signer(mut self, signer: Arc<dyn TradeSigner>) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    /// Attach a streaming price feed so positions are priced push-based. Ticks
+    /// are consumed via [`spawn_price_consumer`](Self::spawn_price_consumer).
+    pub fn attach_price_feed(&self, subscription: crate::price_stream::PriceSubscription) {
+        *self.price_feed.lock() = Some(subscription);
+    }
+
+    /// Rest a limit order. `price` is an ETH quote; the order fills when the
+    /// live price crosses it (buys at or below, sells at or above). Returns the
+    /// order id for later cancellation.
+    pub fn place_limit_order(
+        &self,
+        token_address: &str,
+        side: crate::order_book::OrderSide,
+        price: f64,
+        amount: Amount,
+    ) -> u64 {
+        let id = self.order_book.lock().place(
+            token_address,
+            side,
+            scale_price(price),
+            amount,
+            now_secs(),
+        );
+        tracing::info!(
+            "Limit {:?} order #{} rested: {} {} @ {:.8} ETH",
+            side,
+            id,
+            amount.to_f64(),
+            token_address,
+            price
+        );
+        id
+    }
+
+    pub fn cancel_limit_order(&self, id: u64) -> bool {
+        self.order_book.lock().cancel(id)
+    }
+
+    pub fn list_limit_orders(&self) -> Vec<crate::order_book::LimitOrder> {
+        self.order_book.lock().list()
+    }
+
+    /// Fill any resting orders for `token_address` that the live price has
+    /// crossed: route a filled buy into a new [`Position`] and a filled sell
+    /// into a [`close_position`](Self::close_position).
+    async fn match_limit_orders(&self, token_address: &str, price_scaled: U256) -> Result<()> {
+        let fills = self.order_book.lock().take_crossed(token_address, price_scaled);
+        for order in fills {
+            match order.side {
+                crate::order_book::OrderSide::Buy => {
+                    // Default EIP-1559 fee; the gas oracle can tune this later.
+                    self.execute_buy(&order.token_address, order.amount, 2_000_000_000, 1_000_000_000).await?;
+                }
+                crate::order_book::OrderSide::Sell => {
+                    let position = self.positions.write().await.remove(&order.token_address);
+                    if let Some(position) = position {
+                        self.close_position(position).await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn execute_buy(
+        &self,
+        token_address: &str,
+        amount: Amount,
+        max_fee_per_gas: u64,
+        max_priority_fee_per_gas: u64,
+    ) -> Result<bool> {
+        // Derive this trade's risk parameters from live market state before
+        // sizing. Liquidity drives slippage and size; volatility drives the
+        // stop/take bands. A failed lookup is "unknown", not "zero" — they
+        // drive opposite ends of the thinness scaling in `params_for`.
+        let liquidity = self.okx_client.get_token_liquidity(token_address).await.ok();
+        let market = crate::risk_config::MarketState {
+            liquidity,
+            volatility: self.risk_config.default_volatility,
+        };
+        let params = self.risk_config.params_for(token_address, market);
+        tracing::info!(
+            "Risk params for {}: slippage={:.2}% stop={:.2}% take={:.2}x size={:.2}% (liquidity={:?})",
+            token_address,
+            params.slippage * 100.0,
+            params.stop_loss_pct * 100.0,
+            params.take_profit_mult,
+            params.max_position_size * 100.0,
+            liquidity
+        );
+
+        let current_capital = self.capital.lock().raw;
+        let max_amount = current_capital * U256::from((params.max_position_size * 100.0) as u64) / U256::from(100);
+        let actual = Amount::from_wei(amount.raw.min(max_amount));
+
+        // Dust check is now an exact wei comparison, not a float threshold.
+        if actual.is_dust() {
+            return Ok(false);
+        }
+
+        let positions = self.positions.read().await;
+        if positions.len() >= self.risk_config.max_positions {
+            return Ok(false); // Too many positions
+        }
+        if positions.contains_key(token_address) {
+            return Ok(false); // Already have position
+        }
+        drop(positions);
+
+        let trade_params = TradeParams {
+            token_address: token_address.to_string(),
+            amount_in: actual,
+            slippage_tolerance: params.slippage,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        };
+
+        match self.signer.execute_buy_order(trade_params).await {
+            Ok(result) => {
+                let entry_price = scale_price(result.effective_price);
+                let position = Position {
+                    token_address: token_address.to_string(),
+                    entry_price,
+                    amount: actual,
+                    timestamp: now_secs(),
+                    stop_loss: entry_price * U256::from(((1.0 - params.stop_loss_pct) * 100.0) as u64) / U256::from(100),
+                    take_profit: entry_price * U256::from((params.take_profit_mult * 100.0) as u64) / U256::from(100),
+                    current_value: actual,
+                    unrealized_pnl: Amount::zero(),
+                    unrealized_loss: false,
+                    peak_price: entry_price,
+                    // Trail the same distance as the initial stop, expressed as
+                    // a percent below the running peak.
+                    trailing_stop_pct: (params.stop_loss_pct * 100.0) as u64,
+                    ladder: vec![
+                        TakeProfitRung { trigger_mult_x100: 200, fraction_pct: 25, filled: false },
+                        TakeProfitRung { trigger_mult_x100: 500, fraction_pct: 25, filled: false },
+                    ],
+                    realized_pnl: 0.0,
+                };
+
+                let mut positions = self.positions.write().await;
+                positions.insert(token_address.to_string(), position);
+
+                // Start streaming this token's price the moment we're exposed.
+                if let Some(feed) = self.price_feed.lock().as_ref() {
+                    feed.add(token_address);
+                }
+
+                {
+                    let mut capital = self.capital.lock();
+                    capital.raw = capital.raw.saturating_sub(actual.raw);
+                }
+
+                self.metrics.lock().total_trades += 1;
+
+                tracing::info!(
+                    "Position opened: {} @ {:.8} ETH (Amount: {:.4})",
+                    token_address,
+                    result.effective_price,
+                    actual.to_f64()
+                );
+
+                Ok(true)
+            }
+            Err(e) => {
+                tracing::error!("Trade execution failed: {}", e);
+                Ok(false)
+            }
+        }
+    }
+
+    pub async fn update_positions(&self) -> Result<()> {
+        let tokens: Vec<String> = {
+            let positions = self.positions.read().await;
+            positions.keys().cloned().collect()
+        };
+
+        for token_addr in tokens {
+            if let Ok(current_price) = self.okx_client.get_token_price(&token_addr).await {
+                self.process_position_exits(&token_addr, scale_price(current_price)).await?;
+            }
+        }
+
+        self.refresh_unrealized().await;
+        Ok(())
+    }
+
+    async fn close_position(&self, position: Position) -> Result<()> {
+        let current_price = self.okx_client.get_token_price(&position.token_address).await?;
+        let price_scaled = scale_price(current_price);
+
+        let exit_value = position
+            .amount
+            .scale_ratio(price_scaled, position.entry_price)
+            .unwrap_or(position.amount);
+
+        {
+            let mut capital = self.capital.lock();
+            capital.raw += exit_value.raw;
+        }
+
+        let pnl = exit_value.to_f64() - position.amount.to_f64();
+        let mut metrics = self.metrics.lock();
+        metrics.total_pnl += pnl;
+        // Only the residual pnl is unbanked here — partial_sell already folded
+        // position.realized_pnl into metrics.realized_pnl as each ladder rung
+        // filled.
+        metrics.realized_pnl += pnl;
+        // Count the trade as a winner on its full round-trip, including any
+        // partial exits already banked.
+        if pnl + position.realized_pnl > 0.0 {
+            metrics.winning_trades += 1;
+        }
+        metrics.win_rate = metrics.winning_trades as f64 / metrics.total_trades.max(1) as f64;
+
+        // No longer exposed: drop this token from the live price subscription.
+        if let Some(feed) = self.price_feed.lock().as_ref() {
+            feed.remove(&position.token_address);
+        }
+
+        tracing::info!(
+            "Position closed: {} | PnL: {:.4} ETH",
+            position.token_address,
+            pnl
+        );
+
+        Ok(())
+    }
+
+    pub async fn get_portfolio_summary(&self) -> Result<serde_json::Value> {
+        let positions = self.positions.read().await;
+        let current_capital = self.capital.lock().to_f64();
+        let metrics = self.metrics.lock();
+
+        let mut total_value = current_capital;
+        for position in positions.values() {
+            total_value += position.current_value.to_f64();
+        }
+
+        let total_return = ((total_value - 1000.0) / 1000.0) * 100.0;
+
+        Ok(serde_json::json!({
+            "current_capital": current_capital,
+            "total_value": total_value,
+            "total_return_pct": total_return,
+            "active_positions": positions.len(),
+            "total_trades": metrics.total_trades,
+            "winning_trades": metrics.winning_trades,
+            "win_rate": metrics.win_rate,
+            "total_pnl": metrics.total_pnl,
+            "realized_pnl": metrics.realized_pnl,
+            "unrealized_pnl": metrics.unrealized_pnl,
+            "positions": positions.values().collect::<Vec<_>>(),
+            "open_orders": self.order_book.lock().list()
+        }))
+    }
+
+    pub async fn emergency_close_all(&self) -> Result<()> {
+        let mut positions = self.positions.write().await;
+        let position_list: Vec<Position> = positions.drain().map(|(_, pos)| pos).collect();
+        drop(positions);
+
+        for position in position_list {
+            self.close_position(position).await?;
+        }
+
+        tracing::warn!("Emergency close executed for all positions");
+        Ok(())
+    }
+
+    /// Serialize the full engine state (positions, capital, metrics) into a
+    /// password-encrypted file written atomically. Used so a restart doesn't
+    /// drop open positions or realized PnL.
+    pub async fn backup(&self, path: impl AsRef<std::path::Path>, password: &str) -> Result<()> {
+        let snapshot = {
+            let positions = self.positions.read().await;
+            EngineSnapshot {
+                version: crate::snapshot::SCHEMA_VERSION,
+                positions: positions.clone(),
+                capital: *self.capital.lock(),
+                metrics: self.metrics.lock().clone(),
+            }
+        };
+
+        let plaintext = serde_json::to_vec(&snapshot)?;
+        let blob = crate::snapshot::seal(&plaintext, password)?;
+        crate::snapshot::write_atomic(path, &blob)?;
+        Ok(())
+    }
+
+    /// Decrypt and validate a snapshot written by [`backup`], then rebuild the
+    /// in-memory state in place. Existing positions/capital/metrics are
+    /// replaced wholesale.
+    pub async fn restore(&self, path: impl AsRef<std::path::Path>, password: &str) -> Result<()> {
+        let blob = std::fs::read(path)?;
+        let plaintext = crate::snapshot::open(&blob, password)?;
+        let snapshot: EngineSnapshot = serde_json::from_slice(&plaintext)?;
+        if snapshot.version != crate::snapshot::SCHEMA_VERSION {
+            return Err(anyhow!(
+                "snapshot schema {} is incompatible with {}",
+                snapshot.version,
+                crate::snapshot::SCHEMA_VERSION
+            ));
+        }
+
+        {
+            let mut positions = self.positions.write().await;
+            *positions = snapshot.positions;
+        }
+        *self.capital.lock() = snapshot.capital;
+        *self.metrics.lock() = snapshot.metrics;
+        Ok(())
+    }
+
+    pub fn get_current_capital(&self) -> Amount {
+        *self.capital.lock()
+    }
+
+    pub async fn get_position_count(&self) -> usize {
+        self.positions.read().await.len()
+    }
+}
+
+/// Configuration for the background position-sync task.
+#[derive(Debug, Clone)]
+pub struct SyncConfig {
+    pub interval: Duration,
+    pub max_concurrency: usize,
+    pub enabled: bool,
+    /// Optional sink for status events emitted after each sync tick.
+    pub status_tx: Option<mpsc::UnboundedSender<serde_json::Value>>,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(15),
+            max_concurrency: 8,
+            enabled: true,
+            status_tx: None,
+        }
+    }
+}
+
+/// Handle to a running background sync task; drops the task on stop and lets the
+/// caller pause/resume without tearing it down.
+pub struct SyncHandle {
+    task: tokio::task::JoinHandle<()>,
+    paused: Arc<AtomicBool>,
+}
+
+impl SyncHandle {
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+impl ExecutionEngine {
+    /// Spawn a background task that periodically re-prices open positions
+    /// concurrently, evaluates exit conditions, and emits a status event. Only
+    /// one sync runs at a time — a tick is skipped if the previous sync is still
+    /// in flight — and the task honours the pause/resume flag on its handle.
+    pub fn spawn_background_sync(self: &Arc<Self>, config: SyncConfig) -> SyncHandle {
+        let paused = Arc::new(AtomicBool::new(false));
+        let in_flight = Arc::new(AtomicBool::new(false));
+        let engine = self.clone();
+        let paused_task = paused.clone();
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.interval);
+            loop {
+                ticker.tick().await;
+                if !config.enabled || paused_task.load(Ordering::Relaxed) {
+                    continue;
+                }
+                // Skip this tick if a sync from a previous tick is still running.
+                if in_flight.swap(true, Ordering::AcqRel) {
+                    continue;
+                }
+
+                let engine = engine.clone();
+                let status_tx = config.status_tx.clone();
+                let in_flight = in_flight.clone();
+                let concurrency = config.max_concurrency;
+                tokio::spawn(async move {
+                    if let Err(e) = engine.sync_once(concurrency).await {
+                        tracing::warn!("background sync failed: {e}");
+                    }
+                    if let Some(tx) = status_tx {
+                        if let Ok(status) = engine.get_portfolio_summary().await {
+                            let _ = tx.send(status);
+                        }
+                    }
+                    in_flight.store(false, Ordering::Release);
+                });
+            }
+        });
+
+        SyncHandle { task, paused }
+    }
+
+    /// Drain a [`PriceStream`](crate::price_stream::PriceStream) tick channel,
+    /// evaluating exit conditions for each token the instant a tick arrives
+    /// rather than waiting for the next sync cadence.
+    pub fn spawn_price_consumer(
+        self: &Arc<Self>,
+        mut ticks: mpsc::UnboundedReceiver<crate::price_stream::PriceTick>,
+    ) -> tokio::task::JoinHandle<()> {
+        let engine = self.clone();
+        tokio::spawn(async move {
+            while let Some(tick) = ticks.recv().await {
+                if let Err(e) = engine.apply_tick(&tick).await {
+                    tracing::warn!("failed to apply price tick for {}: {e}", tick.token_address);
+                }
+            }
+        })
+    }
+
+    /// Apply a single streamed price to one position: refresh its mark, fire any
+    /// take-profit ladder rungs it reached as partial sells, and close it
+    /// immediately if the tick crossed its (trailing) stop-loss. Reaching
+    /// `take_profit` alone does not close the position. Then match resting
+    /// limit orders for the token.
+    pub async fn apply_tick(&self, tick: &crate::price_stream::PriceTick) -> Result<()> {
+        let price_scaled = scale_price(tick.price);
+        self.process_position_exits(&tick.token_address, price_scaled).await?;
+        self.match_limit_orders(&tick.token_address, price_scaled).await?;
+        Ok(())
+    }
+
+    /// Mark a single position, ratchet its trailing stop, fire any take-profit
+    /// ladder rungs as partial sells, and fully close it if the (possibly
+    /// raised) stop is hit, it has aged out, or it has been scaled down to dust.
+    async fn process_position_exits(&self, token: &str, price_scaled: U256) -> Result<()> {
+        let mut full_close = false;
+        {
+            let mut positions = self.positions.write().await;
+            let Some(position) = positions.get_mut(token) else {
+                return Ok(());
+            };
+
+            let current_value = position
+                .amount
+                .scale_ratio(price_scaled, position.entry_price)
+                .unwrap_or(position.amount);
+            position.current_value = current_value;
+            if current_value.raw >= position.amount.raw {
+                position.unrealized_pnl = Amount::from_wei(current_value.raw - position.amount.raw);
+                position.unrealized_loss = false;
+            } else {
+                position.unrealized_pnl = Amount::from_wei(position.amount.raw - current_value.raw);
+                position.unrealized_loss = true;
+            }
+
+            // Ratchet the trailing stop up with a new peak; never lower it.
+            if price_scaled > position.peak_price {
+                position.peak_price = price_scaled;
+            }
+            let trail = position.peak_price
+                * U256::from(100 - position.trailing_stop_pct.min(99))
+                / U256::from(100);
+            if trail > position.stop_loss {
+                position.stop_loss = trail;
+            }
+
+            // Fire any ladder rungs the price has reached, cheapest trigger first.
+            let rungs: Vec<(usize, u8)> = position
+                .ladder
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| {
+                    !r.filled
+                        && price_scaled
+                            >= position.entry_price * U256::from(r.trigger_mult_x100) / U256::from(100)
+                })
+                .map(|(i, r)| (i, r.fraction_pct))
+                .collect();
+            for (i, frac) in rungs {
+                position.ladder[i].filled = true;
+                self.partial_sell(position, frac, price_scaled).await?;
+            }
+
+            full_close = price_scaled <= position.stop_loss
+                || now_secs() - position.timestamp > 86400
+                || position.amount.is_dust();
+        }
+
+        if full_close {
+            if let Some(position) = self.positions.write().await.remove(token) {
+                self.close_position(position).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sell `fraction_pct` of a position at the current price, banking realized
+    /// PnL and keeping the residual live. Mirrors [`close_position`]'s pricing
+    /// but leaves the position in the book.
+    async fn partial_sell(
+        &self,
+        position: &mut Position,
+        fraction_pct: u8,
+        price_scaled: U256,
+    ) -> Result<()> {
+        let sell_raw = position.amount.raw * U256::from(fraction_pct) / U256::from(100);
+        let sell_amount = Amount::from_wei(sell_raw);
+        if sell_amount.is_dust() {
+            return Ok(());
+        }
+
+        let proceeds = sell_amount
+            .scale_ratio(price_scaled, position.entry_price)
+            .unwrap_or(sell_amount);
+        self.capital.lock().raw += proceeds.raw;
+
+        let pnl = proceeds.to_f64() - sell_amount.to_f64();
+        position.amount = Amount::from_wei(position.amount.raw.saturating_sub(sell_raw));
+        position.realized_pnl += pnl;
+
+        {
+            let mut metrics = self.metrics.lock();
+            metrics.realized_pnl += pnl;
+            metrics.total_pnl += pnl;
+        }
+
+        tracing::info!(
+            "Partial exit: sold {}% of {} for {:.4} ETH (realized {:.4} ETH)",
+            fraction_pct,
+            position.token_address,
+            proceeds.to_f64(),
+            pnl
+        );
+        Ok(())
+    }
+
+    /// Re-price every open position concurrently (bounded by `max_concurrency`),
+    /// then run exits and limit-order matching for each priced token in turn,
+    /// each under its own write lock.
+    pub async fn sync_once(&self, max_concurrency: usize) -> Result<()> {
+        let tokens: Vec<String> = {
+            let positions = self.positions.read().await;
+            positions.keys().cloned().collect()
+        };
+
+        let prices: HashMap<String, f64> = futures_util::stream::iter(tokens)
+            .map(|token| async move {
+                let price = self.okx_client.get_token_price(&token).await.ok();
+                (token, price)
+            })
+            .buffer_unordered(max_concurrency.max(1))
+            .filter_map(|(token, price)| async move { price.map(|p| (token, p)) })
+            .collect()
+            .await;
+
+        for (token_addr, &current_price) in &prices {
+            let price_scaled = scale_price(current_price);
+            self.process_position_exits(token_addr, price_scaled).await?;
+            // Match resting limit orders against the freshly polled prices.
+            self.match_limit_orders(token_addr, price_scaled).await?;
+        }
+
+        self.refresh_unrealized().await;
+        Ok(())
+    }
+
+    /// Recompute the aggregate unrealized PnL across open positions into
+    /// [`TradeMetrics`], so the portfolio summary separates it from realized.
+    async fn refresh_unrealized(&self) {
+        let positions = self.positions.read().await;
+        let unrealized: f64 = positions
+            .values()
+            .map(|p| {
+                if p.unrealized_loss {
+                    -p.unrealized_pnl.to_f64()
+                } else {
+                    p.unrealized_pnl.to_f64()
+                }
+            })
+            .sum();
+        self.metrics.lock().unrealized_pnl = unrealized;
+    }
+}
+
+/// Scale a floating-point ETH price into a 1e18 fixed-point `U256` so value math
+/// can stay in integer space.
+fn scale_price(price: f64) -> U256 {
+    let scaled = (price * 10f64.powi(PRICE_SCALE as i32)).round();
+    if scaled <= 0.0 {
+        U256::one()
+    } else {
+        U256::from(scaled as u128)
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2930::AccessList;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::U256;
+
+/// An EIP-1559 fee quote: a priority tip plus the total cap the sender is
+/// willing to pay per gas unit.
+#[derive(Debug, Clone, Copy)]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+impl Eip1559Fees {
+    /// Legacy callers (e.g. the OKX swap endpoint) still want a single gas
+    /// price; the cap is the most we would ever pay, so use it.
+    pub fn as_legacy_gas_price(&self) -> U256 {
+        self.max_fee_per_gas
+    }
+}
+
+/// How a chain's gas should be priced. `Legacy` keeps the flat gas-price model
+/// for chains without EIP-1559; `Eip1559` derives fees from `eth_feeHistory`,
+/// scaling the base fee by `base_fee_multiplier` and averaging the
+/// `tip_percentile` priority-fee reward over recent blocks. Per-chain
+/// configuration lives on whichever executor holds the `GasOracle`.
+#[derive(Debug, Clone, Copy)]
+pub enum GasStrategy {
+    Legacy,
+    Eip1559 {
+        /// Multiple of the current base fee to cap at (e.g. `2.0` covers one
+        /// doubling across the next block).
+        base_fee_multiplier: f64,
+        /// `eth_feeHistory` reward percentile to use for the priority tip
+        /// (e.g. `75.0`).
+        tip_percentile: f64,
+    },
+}
+
+impl Default for GasStrategy {
+    /// Matches the fixed `2 * baseFee + p75 tip` formula this oracle already
+    /// used before the strategy became configurable.
+    fn default() -> Self {
+        GasStrategy::Eip1559 { base_fee_multiplier: 2.0, tip_percentile: 75.0 }
+    }
+}
+
+/// Dynamic gas oracle that reads the current base fee and priority tip from the
+/// node instead of relying on a hardcoded constant.
+pub struct GasOracle<M> {
+    provider: M,
+}
+
+impl<M: Middleware> GasOracle<M>
+where
+    M::Error: 'static,
+{
+    pub fn new(provider: M) -> Self {
+        Self { provider }
+    }
+
+    /// Estimate EIP-1559 fees from recent blocks, then bump the priority tip by
+    /// `tip_bump_pct` so mirror trades land ahead of the wallet we copy.
+    pub async fn estimate(&self, tip_bump_pct: u64) -> Result<Eip1559Fees> {
+        let (max_fee, max_priority) = self
+            .provider
+            .estimate_eip1559_fees(None)
+            .await
+            .map_err(|e| anyhow::anyhow!("eip1559 fee estimation failed: {e}"))?;
+
+        let bumped_priority = max_priority + max_priority * U256::from(tip_bump_pct) / U256::from(100);
+        // Raise the cap by the same tip delta so it never falls below the tip.
+        let max_fee = max_fee + (bumped_priority - max_priority);
+
+        Ok(Eip1559Fees {
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: bumped_priority,
+        })
+    }
+
+    /// Price a transaction from `eth_feeHistory` rather than bumping a legacy gas
+    /// price. Reads the latest base fee and the `reward_percentile` priority tip
+    /// over `blocks` recent blocks, then sets the cap to `2 * baseFee + tip`.
+    pub async fn estimate_from_fee_history(&self, blocks: u64, reward_percentile: f64) -> Result<Eip1559Fees> {
+        self.estimate_from_fee_history_scaled(blocks, reward_percentile, 2.0).await
+    }
+
+    /// Same as [`estimate_from_fee_history`](Self::estimate_from_fee_history) but
+    /// with the base-fee headroom multiplier exposed, so [`GasStrategy::Eip1559`]
+    /// can tune how much base-fee drift a quote covers.
+    async fn estimate_from_fee_history_scaled(
+        &self,
+        blocks: u64,
+        reward_percentile: f64,
+        base_fee_multiplier: f64,
+    ) -> Result<Eip1559Fees> {
+        let history = self
+            .provider
+            .fee_history(blocks, ethers::types::BlockNumber::Latest, &[reward_percentile])
+            .await
+            .map_err(|e| anyhow::anyhow!("eth_feeHistory failed: {e}"))?;
+
+        // `base_fee_per_gas` has one more entry than the block count: the last is
+        // the pending block's base fee.
+        let base_fee = history
+            .base_fee_per_gas
+            .last()
+            .copied()
+            .unwrap_or_default();
+
+        // Average the requested priority-tip percentile across the window.
+        let (sum, count) = history
+            .reward
+            .iter()
+            .filter_map(|row| row.first().copied())
+            .fold((U256::zero(), 0u64), |(sum, count), tip| (sum + tip, count + 1));
+        let tip = if count > 0 { sum / U256::from(count) } else { U256::zero() };
+
+        let base_fee_scaled = base_fee * U256::from((base_fee_multiplier * 1000.0) as u64) / U256::from(1000);
+
+        Ok(Eip1559Fees {
+            max_fee_per_gas: base_fee_scaled + tip,
+            max_priority_fee_per_gas: tip,
+        })
+    }
+
+    /// Price a transaction per a configured [`GasStrategy`]. `Legacy` falls back
+    /// to the node's flat `eth_gasPrice` used as both the cap and the tip;
+    /// `Eip1559` derives the quote from `eth_feeHistory` over the last 5 blocks.
+    pub async fn estimate_for_strategy(&self, strategy: GasStrategy) -> Result<Eip1559Fees> {
+        match strategy {
+            GasStrategy::Legacy => {
+                let gas_price = self
+                    .provider
+                    .get_gas_price()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("eth_gasPrice failed: {e}"))?;
+                Ok(Eip1559Fees { max_fee_per_gas: gas_price, max_priority_fee_per_gas: gas_price })
+            }
+            GasStrategy::Eip1559 { base_fee_multiplier, tip_percentile } => {
+                self.estimate_from_fee_history_scaled(5, tip_percentile, base_fee_multiplier).await
+            }
+        }
+    }
+
+    /// Prefetch the storage slots `tx` will touch via `eth_createAccessList` so
+    /// the resulting EIP-2930 access list can be attached before submission,
+    /// avoiding cold-`SLOAD`/`SSTORE` surcharges and the gas-estimate padding
+    /// that comes with them. Returns an empty list (not an error) if the node
+    /// doesn't support the call, since an access list is an optimization, not a
+    /// correctness requirement.
+    pub async fn prefetch_access_list(&self, tx: &TypedTransaction) -> AccessList {
+        match self.provider.create_access_list(tx, None).await {
+            Ok(result) => result.access_list,
+            Err(e) => {
+                tracing::debug!("eth_createAccessList unavailable, skipping prefetch: {e}");
+                AccessList::default()
+            }
+        }
+    }
+}
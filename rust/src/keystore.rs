@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12; // ChaCha20-Poly1305's standard 96-bit nonce.
+
+/// Secrets persisted by the bot. Everything in here is considered sensitive and
+/// is written to disk encrypted through [`Keystore::encrypt`]/[`Keystore::save`];
+/// only the explicit, one-way [`Keystore::decrypt`] writes it in plaintext.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Secrets {
+    pub okx_api_key: String,
+    pub okx_secret_key: String,
+    pub okx_passphrase: String,
+    /// Hex-encoded secp256k1 signing key used by the on-chain executor.
+    pub signing_key: Option<String>,
+    /// Free-form extras so new secrets don't force a file-format migration.
+    #[serde(default)]
+    pub extra: HashMap<String, String>,
+}
+
+/// Password-encrypted keystore for OKX credentials and signing keys, so the
+/// bot never needs plaintext secrets in its environment.
+///
+/// On disk the file is `salt || nonce || ciphertext`, where the ciphertext is a
+/// ChaCha20-Poly1305-sealed JSON blob and the 256-bit key is derived from the
+/// operator password with Argon2id, matching the seed/key-encryption pattern
+/// common to self-custodied wallets: secrets sit encrypted at rest and are
+/// only unlocked into memory for the duration of a session. The plaintext
+/// never touches disk except via the explicit, one-way [`Keystore::decrypt`].
+pub struct Keystore {
+    path: PathBuf,
+    password: String,
+    secrets: Secrets,
+}
+
+impl Keystore {
+    /// Encrypt `secrets` under `password` and write them to `path` for the
+    /// first time, returning the keystore already unlocked for this session.
+    pub fn encrypt(path: impl AsRef<Path>, password: &str, secrets: Secrets) -> Result<Self> {
+        let keystore = Self {
+            path: path.as_ref().to_path_buf(),
+            password: password.to_string(),
+            secrets,
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Decrypt the keystore at `path` with `password`, unlocking it into
+    /// memory for the session. The file on disk is untouched.
+    pub fn unlock(path: impl AsRef<Path>, password: &str) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let blob = std::fs::read(&path)?;
+        if blob.len() < SALT_LEN + NONCE_LEN {
+            return Err(anyhow!("keystore {} is truncated", path.display()));
+        }
+
+        let (salt, rest) = blob.split_at(SALT_LEN);
+        let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let key = derive_key(password, salt)?;
+        let cipher = ChaCha20Poly1305::new((&key).into());
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| anyhow!("failed to decrypt keystore (wrong password?)"))?;
+
+        let secrets: Secrets = serde_json::from_slice(&plaintext)?;
+        Ok(Self { path, password: password.to_string(), secrets })
+    }
+
+    /// Unlock the keystore if it exists, otherwise start an empty one at
+    /// `path` ready to be [`encrypt`](Keystore::encrypt)ed on first [`save`](Keystore::save).
+    pub fn unlock_or_create(path: impl AsRef<Path>, password: &str) -> Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::unlock(path, password)
+        } else {
+            Ok(Self {
+                path: path.to_path_buf(),
+                password: password.to_string(),
+                secrets: Secrets::default(),
+            })
+        }
+    }
+
+    /// Load a keystore from the conventional location, driven by
+    /// `KEYSTORE_PATH` and `KEYSTORE_PASSWORD`.
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var("KEYSTORE_PATH")
+            .unwrap_or_else(|_| "data/keystore.bin".to_string());
+        let password = std::env::var("KEYSTORE_PASSWORD")
+            .map_err(|_| anyhow!("KEYSTORE_PASSWORD not set"))?;
+        Self::unlock(path, &password)
+    }
+
+    pub fn secrets(&self) -> &Secrets {
+        &self.secrets
+    }
+
+    pub fn secrets_mut(&mut self) -> &mut Secrets {
+        &mut self.secrets
+    }
+
+    /// Re-encrypt the current secrets under a fresh salt and nonce and write
+    /// them back to disk.
+    pub fn save(&self) -> Result<()> {
+        let mut rng = rand::thread_rng();
+
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill_bytes(&mut salt);
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce_bytes);
+
+        let key = derive_key(&self.password, &salt)?;
+        let cipher = ChaCha20Poly1305::new((&key).into());
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), serde_json::to_vec(&self.secrets)?.as_slice())
+            .map_err(|_| anyhow!("failed to encrypt keystore"))?;
+
+        let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+        blob.extend_from_slice(&salt);
+        blob.extend_from_slice(&nonce_bytes);
+        blob.extend_from_slice(&ciphertext);
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, blob)?;
+        Ok(())
+    }
+
+    /// Permanently remove encryption: write the secrets to `path` as plain
+    /// JSON and leave the keystore unusable (the in-memory password is
+    /// dropped). One-way — there is no re-encrypting this instance afterward;
+    /// call [`Keystore::encrypt`] again to start a fresh encrypted keystore.
+    pub fn decrypt(self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.secrets)?)?;
+        Ok(())
+    }
+}
+
+/// Base64 helper so tooling can round-trip the sealed blob through stdout.
+pub fn encode_blob(blob: &[u8]) -> String {
+    BASE64.encode(blob)
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("key derivation failed: {e}"))?;
+    Ok(key)
+}
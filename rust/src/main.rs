@@ -1,12 +1,38 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
-use ethers::{prelude::*, providers::{Provider, Http}, types::{Address, U256, U64}};
+use ethers::{prelude::*, providers::{Provider, Http, Ws}, types::{Address, H256, U256, U64}};
+use futures_util::StreamExt;
 use std::sync::Arc;
-use parking_lot::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::VecDeque;
+use std::time::Duration;
+use parking_lot::{Mutex, RwLock};
 use dashmap::DashMap;
 use crossbeam::channel;
 use lru::LruCache;
 use std::num::NonZeroUsize;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::control_server::{ControlHandler, ControlServer};
+
+/// Bound on the `recent_signals` ring buffer the control server's `signals`
+/// query reads from.
+const RECENT_SIGNALS_CAPACITY: usize = 200;
+
+/// Function selectors for the router entrypoints we mirror. Kept in sync with
+/// the decoder in `mempool_scanner` so the two paths agree on what counts as a swap.
+const SWAP_SELECTORS: &[(&str, TradeAction)] = &[
+    ("7ff36ab5", TradeAction::Buy), // swapExactETHForTokens
+    ("b6f9de95", TradeAction::Buy), // swapExactETHForTokensSupportingFeeOnTransferTokens
+    ("38ed1739", TradeAction::Buy), // swapExactTokensForTokens
+    ("18cbafe5", TradeAction::Sell), // swapExactTokensForETH
+    ("791ac947", TradeAction::Sell), // swapExactTokensForETHSupportingFeeOnTransferTokens
+    ("414bf389", TradeAction::Buy), // Uniswap V3 exactInputSingle
+    ("c04b8d59", TradeAction::Buy), // Uniswap V3 exactInput
+    ("12aa3caf", TradeAction::Buy), // 1inch V5 swap
+    ("415565b0", TradeAction::Buy), // 0x transformERC20
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct AlphaWallet {
@@ -31,7 +57,7 @@ struct TokenMetrics {
     transfer_tax: u8,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TradeSignal {
     token_address: String,
     wallet_address: String,
@@ -41,7 +67,7 @@ struct TradeSignal {
     confidence: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum TradeAction {
     Buy,
     Sell,
@@ -52,28 +78,53 @@ struct AlphaMirror {
     alpha_wallets: Arc<DashMap<String, AlphaWallet>>,
     token_cache: Arc<RwLock<LruCache<String, TokenMetrics>>>,
     provider: Arc<Provider<Http>>,
+    ws_url: String,
+    seen_hashes: Arc<DashMap<H256, ()>>,
     http_client: Client,
     signal_tx: channel::Sender<TradeSignal>,
     signal_rx: channel::Receiver<TradeSignal>,
     okx_dex_router: Address,
     current_capital: Arc<RwLock<U256>>,
+    /// Gates `process_signals`: while `true`, signals are still drained off
+    /// the channel (so mirroring doesn't back up) but dropped instead of
+    /// acted on. Toggled by the control server's `pause`/`resume`/`set_paused`.
+    paused: Arc<AtomicBool>,
+    /// Fraction of capital a mirrored buy is sized against; tunable live via
+    /// `set_position_size` instead of a hardcoded constant.
+    position_size_pct: Arc<RwLock<f64>>,
+    /// Minimum wallet win rate required to mirror a signal; tunable live via
+    /// `set_win_rate_threshold`.
+    win_rate_threshold: Arc<RwLock<f64>>,
+    /// Token addresses vetoed by an operator; any matching signal is dropped.
+    vetoed_tokens: Arc<DashMap<String, ()>>,
+    /// Ring buffer of the most recent signals (mirrored or injected), newest
+    /// first, for the `signals` control-server query.
+    recent_signals: Arc<Mutex<VecDeque<TradeSignal>>>,
 }
 
 impl AlphaMirror {
     async fn new() -> anyhow::Result<Self> {
         let http_url = std::env::var("ETH_HTTP_URL").unwrap_or_else(|_| "https://eth-mainnet.alchemyapi.io/v2/demo".to_string());
+        let ws_url = std::env::var("ETH_WS_URL").unwrap_or_else(|_| "wss://eth-mainnet.alchemyapi.io/v2/demo".to_string());
         let provider = Provider::<Http>::try_from(&http_url)?;
         let (signal_tx, signal_rx) = channel::unbounded();
-        
+
         Ok(Self {
             alpha_wallets: Arc::new(DashMap::new()),
             token_cache: Arc::new(RwLock::new(LruCache::new(NonZeroUsize::new(10000).unwrap()))),
             provider: Arc::new(provider),
+            ws_url,
+            seen_hashes: Arc::new(DashMap::new()),
             http_client: Client::new(),
             signal_tx,
             signal_rx,
             okx_dex_router: "0x1111111254EEB25477B68fb85Ed929f73A960582".parse()?,
             current_capital: Arc::new(RwLock::new(U256::from(1000) * U256::exp10(18))),
+            paused: Arc::new(AtomicBool::new(false)),
+            position_size_pct: Arc::new(RwLock::new(0.3)),
+            win_rate_threshold: Arc::new(RwLock::new(0.0)),
+            vetoed_tokens: Arc::new(DashMap::new()),
+            recent_signals: Arc::new(Mutex::new(VecDeque::with_capacity(RECENT_SIGNALS_CAPACITY))),
         })
     }
 
@@ -91,54 +142,279 @@ impl AlphaMirror {
 
     async fn monitor_mempool(&self) -> anyhow::Result<()> {
         println!("👀 Monitoring mempool for alpha wallet activity...");
-        let mut counter = 0u64;
-        
+
+        let mut backoff = Duration::from_secs(1);
         loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-            
-            match self.provider.get_block_number().await {
-                Ok(latest_block) => {
-                    let block_num = latest_block.as_u64();
-                    println!("📊 Block: {} | Tracking {} wallets", block_num, self.alpha_wallets.len());
-                    
-                    if counter % 10 == 0 {
-                        println!("🔍 Alpha wallet activity detected!");
-                        self.simulate_trade_signal().await;
-                    }
+            match self.stream_pending_transactions().await {
+                Ok(()) => {
+                    // A clean end of stream still means we lost the subscription.
+                    println!("⚠️ Pending-tx stream ended, reconnecting...");
+                    // The subscription had been established, so the next
+                    // reconnect starts again from the shortest delay.
+                    backoff = Duration::from_secs(1);
                 }
                 Err(e) => {
-                    println!("⚠️ Error getting block: {}", e);
+                    println!("⚠️ Mempool subscription error: {} (retry in {:?})", e, backoff);
                 }
             }
-            
-            counter += 1;
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_secs(30));
         }
     }
 
-    async fn simulate_trade_signal(&self) {
-        if !self.alpha_wallets.is_empty() {
-            let wallet_count = self.alpha_wallets.len();
-            println!("⚡ Simulated trade signal from {} elite wallets", wallet_count);
+    /// Subscribe to `newPendingTransactions` over WebSocket and mirror any swap
+    /// originating from a tracked alpha wallet. Returns when the stream closes so
+    /// the caller can reconnect with backoff.
+    async fn stream_pending_transactions(&self) -> anyhow::Result<()> {
+        let ws = Provider::<Ws>::connect(&self.ws_url).await?;
+        let mut stream = ws.subscribe_pending_txs().await?;
+        // The subscription is up: if the stream later ends cleanly (`Ok`), the
+        // caller resets its backoff.
+        println!("🔌 Subscribed to pending transactions via {}", self.ws_url);
+
+        while let Some(hash) = stream.next().await {
+            if self.seen_hashes.insert(hash, ()).is_some() {
+                continue;
+            }
+
+            let tx = match ws.get_transaction(hash).await? {
+                Some(tx) => tx,
+                None => continue, // Dropped before we could fetch the body.
+            };
+
+            if let Some(signal) = self.signal_from_tx(&tx) {
+                if self.signal_tx.send(signal).is_err() {
+                    return Ok(()); // Receiver gone; let run() tear down.
+                }
+            }
         }
+
+        Ok(())
+    }
+
+    /// Decode a pending transaction into a `TradeSignal` when it is a known DEX
+    /// swap sent by one of the alpha wallets we mirror.
+    fn signal_from_tx(&self, tx: &Transaction) -> Option<TradeSignal> {
+        let from = format!("{:?}", tx.from).to_lowercase();
+        let wallet = self.alpha_wallets.get(&from)?;
+        if (wallet.win_rate as f64) < *self.win_rate_threshold.read() {
+            return None;
+        }
+
+        let to = tx.to?;
+        let input = tx.input.as_ref();
+        if input.len() < 4 {
+            return None;
+        }
+
+        let selector = hex::encode(&input[0..4]);
+        let action = SWAP_SELECTORS
+            .iter()
+            .find(|(id, _)| *id == selector)
+            .map(|(_, action)| action.clone())?;
+
+        let token_address = decode_swap_token(to, input)?;
+        if self.vetoed_tokens.contains_key(&token_address) {
+            return None;
+        }
+        // Confidence blends the wallet's historical edge and hit rate into [0, 1].
+        let confidence = ((wallet.avg_multiplier / 10.0).min(1.0) as f32 * 0.5)
+            + (wallet.win_rate as f32 * 0.5);
+
+        Some(TradeSignal {
+            token_address,
+            wallet_address: from,
+            action,
+            amount: tx.value,
+            timestamp: now_secs(),
+            confidence: confidence.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Record `signal` in the bounded recent-signals ring buffer the control
+    /// server's `signals` query reads from.
+    fn record_signal(&self, signal: TradeSignal) {
+        let mut recent = self.recent_signals.lock();
+        if recent.len() >= RECENT_SIGNALS_CAPACITY {
+            recent.pop_back();
+        }
+        recent.push_front(signal);
     }
 
     async fn process_signals(&self) -> anyhow::Result<()> {
-        while let Ok(_signal) = self.signal_rx.recv() {
-            println!("⚡ Processing trade signal...");
+        while let Ok(signal) = self.signal_rx.recv() {
+            if self.paused.load(Ordering::Relaxed) {
+                continue; // Drain the channel so mirroring doesn't back up while paused.
+            }
+
+            let capital = self.current_capital.read().as_u128() as f64;
+            let suggested_amount = capital * *self.position_size_pct.read();
+            println!(
+                "⚡ {:?} {} via {} (confidence {:.2}, suggested size {:.0} wei)",
+                signal.action, signal.token_address, signal.wallet_address, signal.confidence, suggested_amount
+            );
+            self.record_signal(signal);
         }
         Ok(())
     }
 
-    async fn run(&self) -> anyhow::Result<()> {
+    /// Runs the mempool monitor and signal processor forever, plus (when
+    /// `CONTROL_SERVER_ADDR` is set) the operator control server, as three
+    /// concurrent tasks. Takes `Arc<Self>` rather than `&self` because the
+    /// control server needs to hold a `Arc<dyn ControlHandler>`-compatible
+    /// clone of the engine alongside the other two tasks.
+    async fn run(self: Arc<Self>) -> anyhow::Result<()> {
         self.load_alpha_wallets().await?;
-        
-        let mempool_monitor = self.monitor_mempool();
-        let signal_processor = self.process_signals();
-        
-        tokio::try_join!(mempool_monitor, signal_processor)?;
-        
+
+        let mempool_monitor = {
+            let this = self.clone();
+            async move { this.monitor_mempool().await }
+        };
+        let signal_processor = {
+            let this = self.clone();
+            async move { this.process_signals().await }
+        };
+        let control_server = {
+            let this = self.clone();
+            async move { this.serve_control_server().await }
+        };
+
+        tokio::try_join!(mempool_monitor, signal_processor, control_server)?;
+
         Ok(())
     }
+
+    /// Spawn the newline-delimited JSON-RPC control server over this engine's
+    /// live state, if `CONTROL_SERVER_ADDR` is configured. A no-op (stays
+    /// pending forever rather than erroring the other two tasks out of
+    /// `try_join!`) when it isn't, so the control server remains opt-in.
+    async fn serve_control_server(self: Arc<Self>) -> anyhow::Result<()> {
+        let addr = match std::env::var("CONTROL_SERVER_ADDR") {
+            Ok(addr) => addr,
+            Err(_) => std::future::pending::<String>().await,
+        };
+
+        let auth_token = std::env::var("CONTROL_SERVER_AUTH_TOKEN").ok();
+        let mut server = ControlServer::new(self);
+        if let Some(token) = auth_token {
+            server = server.with_auth_token(token);
+        }
+        server.serve(&addr).await
+    }
+}
+
+#[async_trait]
+impl ControlHandler for AlphaMirror {
+    async fn status(&self) -> Value {
+        json!({
+            "wallets_tracked": self.alpha_wallets.len(),
+            "paused": self.paused.load(Ordering::Relaxed),
+            "current_capital_wei": self.current_capital.read().to_string(),
+        })
+    }
+
+    async fn set_paused(&self, paused: bool) -> bool {
+        self.paused.store(paused, Ordering::Relaxed);
+        paused
+    }
+
+    async fn metrics(&self) -> Value {
+        // AlphaMirror only mirrors and logs signals in this pipeline -- PnL
+        // and position tracking live in `execution_engine`'s entry point, not
+        // here -- so report what's actually available rather than fake a
+        // metrics shape this engine doesn't track.
+        json!({
+            "wallets_tracked": self.alpha_wallets.len(),
+            "seen_hashes": self.seen_hashes.len(),
+            "recent_signals": self.recent_signals.lock().len(),
+        })
+    }
+
+    async fn close_all(&self) -> usize {
+        // AlphaMirror doesn't submit or hold positions of its own (see
+        // `metrics`), so there's nothing to close.
+        0
+    }
+
+    async fn list_wallets(&self) -> Vec<Value> {
+        self.alpha_wallets.iter().filter_map(|e| serde_json::to_value(e.value()).ok()).collect()
+    }
+
+    async fn add_wallet(&self, wallet: Value) -> bool {
+        let Ok(wallet) = serde_json::from_value::<AlphaWallet>(wallet) else { return false };
+        if self.alpha_wallets.contains_key(&wallet.address) {
+            return false;
+        }
+        self.alpha_wallets.insert(wallet.address.clone(), wallet);
+        true
+    }
+
+    async fn remove_wallet(&self, address: &str) -> bool {
+        self.alpha_wallets.remove(address).is_some()
+    }
+
+    async fn capital(&self) -> Value {
+        json!({ "current_capital_wei": self.current_capital.read().to_string() })
+    }
+
+    async fn recent_signals(&self, limit: usize) -> Vec<Value> {
+        self.recent_signals
+            .lock()
+            .iter()
+            .take(limit)
+            .filter_map(|s| serde_json::to_value(s).ok())
+            .collect()
+    }
+
+    async fn inject_signal(&self, signal: Value) -> bool {
+        let Ok(signal) = serde_json::from_value::<TradeSignal>(signal) else { return false };
+        self.signal_tx.send(signal).is_ok()
+    }
+
+    async fn veto_signal(&self, token_address: &str) -> bool {
+        self.vetoed_tokens.insert(token_address.to_lowercase(), ()).is_none()
+    }
+
+    async fn pending_hashes(&self) -> Vec<String> {
+        self.seen_hashes.iter().map(|e| format!("{:?}", e.key())).collect()
+    }
+
+    async fn open_orders(&self) -> Vec<Value> {
+        // No OkxClient in this pipeline (see `metrics`/`close_all`) to poll
+        // order status against.
+        Vec::new()
+    }
+
+    async fn set_position_size(&self, pct: f64) -> f64 {
+        let mut current = self.position_size_pct.write();
+        let previous = *current;
+        *current = pct;
+        previous
+    }
+
+    async fn set_win_rate_threshold(&self, threshold: f64) -> f64 {
+        let mut current = self.win_rate_threshold.write();
+        let previous = *current;
+        *current = threshold;
+        previous
+    }
+}
+
+/// Recover the swapped token address from router calldata via proper ABI
+/// decoding (not hand-rolled head/tail offset math, which misreads the
+/// Uniswap V2 layout and can panic on attacker-controlled lengths). Delegates
+/// to the same decoder `mempool_scanner` uses, so both paths agree on every
+/// selector, including the Uniswap V3 and 1inch/0x aggregator forms.
+fn decode_swap_token(_router: Address, input: &[u8]) -> Option<String> {
+    crate::mempool_scanner::extract_token_from_calldata(input).ok()
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
 }
 
 #[tokio::main]
@@ -146,7 +422,7 @@ async fn main() -> anyhow::Result<()> {
     println!("🧠 Elite Alpha Mirror Bot - Rust Engine Starting...");
     println!("💰 Target: $1K → $1M through smart money mirroring");
     
-    let mirror = AlphaMirror::new().await?;
+    let mirror = Arc::new(AlphaMirror::new().await?);
     mirror.run().await?;
     Ok(())
 }
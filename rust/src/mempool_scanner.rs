@@ -1,18 +1,36 @@
 use std::sync::Arc;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
 use tokio::sync::mpsc;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use futures_util::{SinkExt, StreamExt};
-use serde_json::Value;
+use serde_json::{json, Value};
 use dashmap::DashMap;
 use web3::types::{H256, Transaction};
+use ethers::abi::{decode, ParamType, Token};
+use ethers::providers::{Middleware, Provider, Ws};
+use ethers::types::H256 as EthersH256;
 use anyhow::{Result, anyhow};
 use crossbeam::channel::{bounded, Receiver, Sender};
+use parking_lot::Mutex;
 use rayon::prelude::*;
 
 use crate::{AlphaWallet, TokenTrade, TradeType};
 use crate::token_validator::TokenValidator;
 use crate::execution_engine::ExecutionEngine;
+use crate::ml::RustMLProcessor;
+
+/// How long a fetched transaction body stays usable in the local cache before we
+/// consider it stale and re-fetch. Pending-tx bodies are immutable once seen, so
+/// this mostly bounds memory rather than correctness.
+const TX_CACHE_TTL: Duration = Duration::from_secs(30);
+
+struct CachedTx {
+    tx: Transaction,
+    fetched_at: Instant,
+}
 
 pub struct MempoolScanner {
     alpha_wallets: Arc<DashMap<String, AlphaWallet>>,
@@ -20,6 +38,41 @@ pub struct MempoolScanner {
     execution: Arc<ExecutionEngine>,
     pending_hashes: Arc<DashMap<String, u64>>,
     dex_routers: HashSet<String>,
+    /// Primary RPC, fanned out over every endpoint in `ETHEREUM_RPC_URLS`
+    /// (falling back to a single `ETHEREUM_RPC_URL`) so one flaky provider
+    /// doesn't stall batch tx fetches or fee pricing.
+    rpc_provider: Arc<crate::web3_client::FailoverProvider>,
+    /// Deliberately *not* part of `rpc_provider`'s failover set: this talks
+    /// only to `LIGHT_CLIENT_RPC_URL`, an independent node used purely to
+    /// cross-check the primary's reported head in `verify_chain_head`.
+    light_client: reqwest::Client,
+    tx_cache: Arc<DashMap<H256, CachedTx>>,
+    /// When the RPC provider supports server-side filtered, full-body pending
+    /// subscriptions (e.g. Alchemy's `alchemy_pendingTransactions`), stream the
+    /// bodies directly and skip the fetch round trip. Toggled by
+    /// `MEMPOOL_FULL_BODY`.
+    full_body: bool,
+    /// Shared ethers-rs WebSocket provider for on-chain contract reads (owner
+    /// checks, access-list prefetch, etc.); the same handle passed to
+    /// [`TokenValidator::new`](crate::token_validator::TokenValidator::new) so
+    /// both share one push-based connection instead of each opening their own.
+    rpc_ws: Arc<Provider<Ws>>,
+    /// Whale-behavior model used as a second, independent cap on position
+    /// sizing alongside the Kelly fraction below.
+    ml: Arc<RustMLProcessor>,
+    /// Fraction of full Kelly to actually risk (`0.5` = half-Kelly), guarding
+    /// against `win_rate`/`avg_multiplier` being noisy estimates.
+    kelly_fraction: f64,
+    /// Ceiling on the sized fraction of capital, regardless of how favorable
+    /// the computed Kelly edge is.
+    max_position_pct: f64,
+    /// Fallback fraction used when the computed Kelly edge is non-positive
+    /// (negative expectancy) rather than skipping the trade outright.
+    min_position_pct: f64,
+    /// Last head `(number, hash)` this scanner itself observed and accepted,
+    /// used to verify parent-hash linkage on the next check in
+    /// [`verify_chain_head`].
+    last_verified_head: Arc<Mutex<Option<(u64, EthersH256)>>>,
 }
 
 impl MempoolScanner {
@@ -27,12 +80,15 @@ impl MempoolScanner {
         alpha_wallets: Arc<DashMap<String, AlphaWallet>>,
         validator: Arc<TokenValidator>,
         execution: Arc<ExecutionEngine>,
+        rpc_ws: Arc<Provider<Ws>>,
     ) -> Self {
         let mut dex_routers = HashSet::new();
         dex_routers.insert("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_lowercase()); // Uniswap V2
         dex_routers.insert("0xE592427A0AEce92De3Edee1F18E0157C05861564".to_lowercase()); // Uniswap V3
         dex_routers.insert("0xd9e1cE17f2641f24aE83637ab66a2cca9C378B9F".to_lowercase()); // SushiSwap
         dex_routers.insert("0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506".to_lowercase()); // SushiSwap Router
+        dex_routers.insert("0x1111111254EEB25477B68fb85Ed929f73A960582".to_lowercase()); // 1inch V5 Aggregation Router
+        dex_routers.insert("0xDef1C0ded9bec7F1a1670819833240f027b25EfF".to_lowercase()); // 0x Exchange Proxy
 
         Self {
             alpha_wallets,
@@ -40,9 +96,28 @@ impl MempoolScanner {
             execution,
             pending_hashes: Arc::new(DashMap::new()),
             dex_routers,
+            rpc_provider: Arc::new(
+                crate::web3_client::FailoverProvider::new(&rpc_urls_from_env())
+                    .expect("at least one RPC endpoint must be configured"),
+            ),
+            light_client: reqwest::Client::new(),
+            tx_cache: Arc::new(DashMap::new()),
+            full_body: std::env::var("MEMPOOL_FULL_BODY").map(|v| v == "1").unwrap_or(false),
+            rpc_ws,
+            ml: Arc::new(RustMLProcessor::new()),
+            kelly_fraction: 0.5,
+            max_position_pct: 0.3,
+            min_position_pct: 0.05,
+            last_verified_head: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Override the default half-Kelly fraction (e.g. tighter for live capital).
+    pub fn with_kelly_fraction(mut self, kelly_fraction: f64) -> Self {
+        self.kelly_fraction = kelly_fraction;
+        self
+    }
+
     pub async fn start(&self) -> Result<()> {
         let ws_url = std::env::var("ETHEREUM_WS_URL")
             .map_err(|_| anyhow!("ETHEREUM_WS_URL not set"))?;
@@ -50,7 +125,7 @@ impl MempoolScanner {
         let (tx_sender, mut tx_receiver) = mpsc::channel::<Transaction>(10000);
         let (trade_sender, trade_receiver) = bounded::<TokenTrade>(1000);
 
-        let scanner_task = self.start_websocket_scanner(ws_url, tx_sender);
+        let scanner_task = self.connection_service(ws_url, tx_sender);
         let processor_task = self.start_transaction_processor(tx_receiver, trade_sender);
         let executor_task = self.start_trade_executor(trade_receiver);
 
@@ -58,6 +133,42 @@ impl MempoolScanner {
         Ok(())
     }
 
+    /// Keep a live pending-tx subscription up for the lifetime of the bot,
+    /// reconnecting with capped exponential backoff whenever the socket drops or
+    /// the provider errors. Replaces the previous one-shot connect that tore
+    /// down the whole pipeline on the first disconnect.
+    async fn connection_service(
+        &self,
+        ws_url: String,
+        tx_sender: mpsc::Sender<Transaction>,
+    ) -> Result<()> {
+        // Prefer a local IPC socket when one is configured: it avoids the
+        // TCP/TLS and framing overhead of WebSocket, shaving latency off every
+        // pending-tx notification.
+        let ipc_path = std::env::var("ETHEREUM_IPC_PATH").ok();
+
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            let started = Instant::now();
+            let result = match &ipc_path {
+                Some(path) => self.start_ipc_scanner(path, tx_sender.clone()).await,
+                None => self.start_websocket_scanner(ws_url.clone(), tx_sender.clone()).await,
+            };
+            match result {
+                Ok(()) => tracing::warn!("mempool subscription closed cleanly, reconnecting"),
+                Err(e) => tracing::warn!("mempool subscription error: {e}, reconnecting"),
+            }
+
+            // A connection that stayed up a while is healthy; reset the backoff.
+            if started.elapsed() > Duration::from_secs(60) {
+                backoff = Duration::from_secs(1);
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_secs(30));
+        }
+    }
+
     async fn start_websocket_scanner(
         &self,
         ws_url: String,
@@ -66,15 +177,8 @@ impl MempoolScanner {
         let (ws_stream, _) = connect_async(&ws_url).await?;
         let (mut write, mut read) = ws_stream.split();
 
-        let subscribe_msg = serde_json::json!({
-            "id": 1,
-            "method": "eth_subscribe",
-            "params": ["pendingTransactions"]
-        });
-
-        write.send(Message::Text(subscribe_msg.to_string())).await?;
+        write.send(Message::Text(self.subscribe_message().to_string())).await?;
 
-        let web3_client = web3::Web3::new(web3::transports::Http::new(&std::env::var("ETHEREUM_RPC_URL")?)?);
         let mut batch_hashes = Vec::with_capacity(100);
         let mut last_batch_time = std::time::Instant::now();
 
@@ -82,22 +186,7 @@ impl MempoolScanner {
             match msg? {
                 Message::Text(text) => {
                     if let Ok(data) = serde_json::from_str::<Value>(&text) {
-                        if let Some(result) = data["params"]["result"].as_str() {
-                            if let Ok(hash) = result.parse::<H256>() {
-                                batch_hashes.push(hash);
-
-                                if batch_hashes.len() >= 50 || last_batch_time.elapsed().as_millis() >= 100 {
-                                    self.process_transaction_batch(
-                                        &web3_client,
-                                        batch_hashes.clone(),
-                                        &tx_sender,
-                                    ).await?;
-                                    
-                                    batch_hashes.clear();
-                                    last_batch_time = std::time::Instant::now();
-                                }
-                            }
-                        }
+                        self.on_notification(&data, &mut batch_hashes, &mut last_batch_time, &tx_sender).await?;
                     }
                 }
                 Message::Close(_) => break,
@@ -107,31 +196,166 @@ impl MempoolScanner {
         Ok(())
     }
 
+    /// Subscribe to pending transactions over a local Geth/Erigon IPC socket.
+    /// IPC skips the TCP/TLS and WebSocket framing used by `start_websocket_scanner`,
+    /// cutting notification latency when the node runs on the same host.
+    async fn start_ipc_scanner(
+        &self,
+        ipc_path: &str,
+        tx_sender: mpsc::Sender<Transaction>,
+    ) -> Result<()> {
+        let stream = UnixStream::connect(ipc_path).await?;
+        let (read_half, mut write_half) = stream.into_split();
+
+        let mut request = self.subscribe_message().to_string();
+        request.push('\n');
+        write_half.write_all(request.as_bytes()).await?;
+
+        let mut lines = BufReader::new(read_half).lines();
+        let mut batch_hashes = Vec::with_capacity(100);
+        let mut last_batch_time = std::time::Instant::now();
+
+        while let Some(line) = lines.next_line().await? {
+            if let Ok(data) = serde_json::from_str::<Value>(&line) {
+                self.on_notification(&data, &mut batch_hashes, &mut last_batch_time, &tx_sender).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The `eth_subscribe` request used by every transport, honouring the
+    /// full-body mode when the provider supports server-side filtering.
+    fn subscribe_message(&self) -> Value {
+        if self.full_body {
+            // Ask the provider to filter by our alpha wallets and return full tx
+            // bodies, so matching txs arrive ready to decode with no fetch.
+            let from: Vec<String> = self
+                .alpha_wallets
+                .iter()
+                .map(|entry| entry.key().clone())
+                .collect();
+            json!({
+                "id": 1,
+                "method": "eth_subscribe",
+                "params": ["alchemy_pendingTransactions", {
+                    "fromAddress": from,
+                    "hashesOnly": false
+                }]
+            })
+        } else {
+            json!({
+                "id": 1,
+                "method": "eth_subscribe",
+                "params": ["pendingTransactions"]
+            })
+        }
+    }
+
+    /// Handle one subscription notification, regardless of transport: forward a
+    /// server-filtered full body directly, or accumulate hash-only notifications
+    /// into a batch that flushes on size or a short time window.
+    async fn on_notification(
+        &self,
+        data: &Value,
+        batch_hashes: &mut Vec<H256>,
+        last_batch_time: &mut std::time::Instant,
+        tx_sender: &mpsc::Sender<Transaction>,
+    ) -> Result<()> {
+        if self.full_body {
+            if let Ok(tx) = serde_json::from_value::<Transaction>(data["params"]["result"].clone()) {
+                let _ = tx_sender.send(tx).await;
+            }
+            return Ok(());
+        }
+
+        if let Some(result) = data["params"]["result"].as_str() {
+            if let Ok(hash) = result.parse::<H256>() {
+                batch_hashes.push(hash);
+
+                if batch_hashes.len() >= 50 || last_batch_time.elapsed().as_millis() >= 100 {
+                    self.process_transaction_batch(batch_hashes.clone(), tx_sender).await?;
+                    batch_hashes.clear();
+                    *last_batch_time = std::time::Instant::now();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve a batch of pending hashes to transaction bodies. Hashes with a
+    /// fresh cache entry are served locally; the remainder are fetched in a
+    /// single `eth_getTransactionByHash` JSON-RPC batch request instead of one
+    /// round trip per hash.
     async fn process_transaction_batch(
         &self,
-        web3_client: &web3::Web3<web3::transports::Http>,
         hashes: Vec<H256>,
         tx_sender: &mpsc::Sender<Transaction>,
     ) -> Result<()> {
-        let futures: Vec<_> = hashes.into_iter().map(|hash| {
-            let client = web3_client.clone();
-            async move {
-                client.eth().transaction(web3::types::TransactionId::Hash(hash)).await
-            }
-        }).collect();
-
-        let results = futures_util::future::join_all(futures).await;
-        
-        for result in results {
-            if let Ok(Some(tx)) = result {
-                if self.is_alpha_wallet_transaction(&tx) {
-                    let _ = tx_sender.send(tx).await;
+        // Drop stale bodies so the cache only holds entries inside the TTL window.
+        self.tx_cache.retain(|_, entry| entry.fetched_at.elapsed() < TX_CACHE_TTL);
+
+        let mut resolved: Vec<Transaction> = Vec::with_capacity(hashes.len());
+        let mut to_fetch: Vec<H256> = Vec::new();
+
+        for hash in hashes {
+            match self.tx_cache.get(&hash) {
+                Some(entry) if entry.fetched_at.elapsed() < TX_CACHE_TTL => {
+                    resolved.push(entry.tx.clone());
                 }
+                _ => to_fetch.push(hash),
+            }
+        }
+
+        if !to_fetch.is_empty() {
+            for tx in self.fetch_transactions(&to_fetch).await? {
+                self.tx_cache.insert(tx.hash, CachedTx { tx: tx.clone(), fetched_at: Instant::now() });
+                resolved.push(tx);
+            }
+        }
+
+        for tx in resolved {
+            if self.is_alpha_wallet_transaction(&tx) {
+                let _ = tx_sender.send(tx).await;
             }
         }
         Ok(())
     }
 
+    /// Issue a single JSON-RPC 2.0 batch for the given hashes and deserialize the
+    /// non-null results into transactions.
+    async fn fetch_transactions(&self, hashes: &[H256]) -> Result<Vec<Transaction>> {
+        let batch: Vec<Value> = hashes
+            .iter()
+            .enumerate()
+            .map(|(i, hash)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": i,
+                    "method": "eth_getTransactionByHash",
+                    "params": [format!("{:#x}", hash)],
+                })
+            })
+            .collect();
+
+        let response = self.rpc_provider.post_raw(&Value::Array(batch)).await?;
+
+        let entries = response
+            .as_array()
+            .ok_or_else(|| anyhow!("unexpected JSON-RPC batch response"))?;
+
+        let mut txs = Vec::with_capacity(entries.len());
+        for entry in entries {
+            if let Some(result) = entry.get("result") {
+                if !result.is_null() {
+                    if let Ok(tx) = serde_json::from_value::<Transaction>(result.clone()) {
+                        txs.push(tx);
+                    }
+                }
+            }
+        }
+        Ok(txs)
+    }
+
     async fn start_transaction_processor(
         &self,
         mut tx_receiver: mpsc::Receiver<Transaction>,
@@ -149,22 +373,62 @@ impl MempoolScanner {
         let validator = self.validator.clone();
         let execution = self.execution.clone();
         let alpha_wallets = self.alpha_wallets.clone();
+        let rpc_provider = self.rpc_provider.clone();
+        let light_client = self.light_client.clone();
+        let rpc_ws = self.rpc_ws.clone();
+        let ml = self.ml.clone();
+        let kelly_fraction = self.kelly_fraction;
+        let max_position_pct = self.max_position_pct;
+        let min_position_pct = self.min_position_pct;
+        let last_verified_head = self.last_verified_head.clone();
 
         tokio::task::spawn_blocking(move || {
             trade_receiver.iter().par_bridge().for_each(|trade| {
                 let validator = validator.clone();
                 let execution = execution.clone();
                 let alpha_wallets = alpha_wallets.clone();
+                let rpc_provider = rpc_provider.clone();
+                let light_client = light_client.clone();
+                let rpc_ws = rpc_ws.clone();
+                let ml = ml.clone();
+                let last_verified_head = last_verified_head.clone();
 
                 tokio::runtime::Handle::current().block_on(async move {
                     if let Some(wallet) = alpha_wallets.get(&trade.wallet_address) {
                         if wallet.win_rate > 0.7 && wallet.avg_multiplier > 5.0 {
+                            // Don't act on a stale or forked view: confirm the
+                            // head our RPC reports is recent, links back to the
+                            // last head we ourselves verified, and agrees with
+                            // the light-client endpoint before spending capital.
+                            if !verify_chain_head(&rpc_ws, &light_client, &last_verified_head).await {
+                                tracing::warn!("skipping trade: chain head failed verification");
+                                return;
+                            }
+
                             if let Ok(true) = validator.validate_token(&trade.token_address).await {
-                                let position_size = 1000.0 * 0.3; // 30% of capital
+                                let position_size = calculate_position_size(
+                                    &wallet,
+                                    &ml,
+                                    kelly_fraction,
+                                    max_position_pct,
+                                    min_position_pct,
+                                    execution.get_current_capital(),
+                                );
+                                // Price from eth_feeHistory (base fee + priority
+                                // tip) rather than bumping the copied tx's legacy
+                                // gas price; fall back to the legacy figure only
+                                // if the fee-history call fails. Keep the tip and
+                                // the cap as separate fields — collapsing them
+                                // would have the tip eat the whole fee cap.
+                                let (max_fee_per_gas, max_priority_fee_per_gas) =
+                                    fee_history_gas_price(&rpc_provider)
+                                        .await
+                                        .unwrap_or((trade.gas_price + 2_000_000_000, 2_000_000_000));
                                 let _ = execution.execute_buy(
                                     &trade.token_address,
                                     position_size,
-                                    trade.gas_price + 2_000_000_000,
+                                    max_fee_per_gas,
+                                    max_priority_fee_per_gas,
                                 ).await;
                             }
                         }
@@ -202,9 +466,14 @@ impl MempoolScanner {
 
                 let trade_type = match method_id.as_str() {
                     "7ff36ab5" => TradeType::Buy, // swapExactETHForTokens
-                    "18cbafe5" => TradeType::Buy, // swapExactETHForTokensSupportingFeeOnTransferTokens
+                    "b6f9de95" => TradeType::Buy, // swapExactETHForTokensSupportingFeeOnTransferTokens
                     "38ed1739" => TradeType::Buy, // swapExactTokensForTokens
-                    "b6f9de95" => TradeType::Buy, // swapExactETHForTokensOut
+                    "18cbafe5" => TradeType::Sell, // swapExactTokensForETH
+                    "791ac947" => TradeType::Sell, // swapExactTokensForETHSupportingFeeOnTransferTokens
+                    "414bf389" => TradeType::Buy, // Uniswap V3 exactInputSingle
+                    "c04b8d59" => TradeType::Buy, // Uniswap V3 exactInput
+                    "12aa3caf" => TradeType::Buy, // 1inch V5 swap
+                    "415565b0" => TradeType::Buy, // 0x transformERC20
                     _ => return Ok(None),
                 };
 
@@ -215,7 +484,7 @@ impl MempoolScanner {
                     token_address,
                     tx_hash: format!("{:?}", tx.hash),
                     amount_eth: tx.value.as_u128() as f64 / 1e18,
-                    gas_price: tx.gas_price.unwrap_or_default().as_u64(),
+                    gas_price: effective_gas_price(tx),
                     timestamp: std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)?
                         .as_secs(),
@@ -227,28 +496,346 @@ impl MempoolScanner {
     }
 
     fn extract_token_from_calldata(&self, calldata: &[u8]) -> Result<String> {
-        if calldata.len() < 4 {
-            return Err(anyhow!("Invalid calldata"));
-        }
-
-        let method_id = &calldata[0..4];
-        
-        match hex::encode(method_id).as_str() {
-            "7ff36ab5" | "18cbafe5" => {
-                if calldata.len() >= 68 {
-                    let path_offset = u32::from_be_bytes([
-                        calldata[4], calldata[5], calldata[6], calldata[7]
-                    ]) as usize;
-                    
-                    if calldata.len() >= path_offset + 32 {
-                        let token_bytes = &calldata[path_offset + 32..path_offset + 52];
-                        return Ok(format!("0x{}", hex::encode(token_bytes)));
+        extract_token_from_calldata(calldata)
+    }
+}
+
+/// Recover the output token address from router calldata using ABI decoding
+/// rather than hand-rolled byte offsets. Covers the Uniswap V2 `path`-based
+/// swaps, the Uniswap V3 `exactInputSingle`/`exactInput` forms (the latter
+/// via its packed path), and the 1inch V5 `swap` / 0x `transformERC20`
+/// aggregator calls. Free function (doesn't need scanner state) so
+/// `main::decode_swap_token` can share it instead of re-deriving offsets by
+/// hand.
+pub(crate) fn extract_token_from_calldata(calldata: &[u8]) -> Result<String> {
+    if calldata.len() < 4 {
+        return Err(anyhow!("Invalid calldata"));
+    }
+
+    let (selector, args) = calldata.split_at(4);
+    match hex::encode(selector).as_str() {
+        // Uniswap V2 ETH-in swaps: (uint amountOutMin, address[] path, address
+        // to, uint deadline). The output token is always the last entry of
+        // the `path` array.
+        "7ff36ab5" | "b6f9de95" => {
+            let params = [
+                ParamType::Uint(256),
+                ParamType::Array(Box::new(ParamType::Address)),
+                ParamType::Address,
+                ParamType::Uint(256),
+            ];
+            let tokens = decode(&params, args)?;
+            last_address_of_path(tokens.get(1))
+        }
+        // swapExactTokensForETH / its fee-on-transfer variant: (uint amountIn,
+        // uint amountOutMin, address[] path, address to, uint deadline) — same
+        // 5-arg shape as swapExactTokensForTokens below, just with an implicit
+        // WETH leg.
+        "38ed1739" | "18cbafe5" | "791ac947" => {
+            let params = [
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Array(Box::new(ParamType::Address)),
+                ParamType::Address,
+                ParamType::Uint(256),
+            ];
+            let tokens = decode(&params, args)?;
+            last_address_of_path(tokens.get(2))
+        }
+        // Uniswap V3 exactInputSingle((tokenIn, tokenOut, fee, recipient,
+        // deadline, amountIn, amountOutMin, sqrtPriceLimitX96)).
+        "414bf389" => {
+            let params = [ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(24),
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(160),
+            ])];
+            let tokens = decode(&params, args)?;
+            if let Some(Token::Tuple(fields)) = tokens.first() {
+                if let Some(Token::Address(addr)) = fields.get(1) {
+                    return Ok(format!("{:?}", addr));
+                }
+            }
+            Err(anyhow!("Could not extract token address"))
+        }
+        // Uniswap V3 exactInput((bytes path, recipient, deadline, amountIn,
+        // amountOutMin)) — path is packed as token(20) fee(3) token(20)...,
+        // so the output token is its final 20 bytes.
+        "c04b8d59" => {
+            let params = [ParamType::Tuple(vec![
+                ParamType::Bytes,
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+            ])];
+            let tokens = decode(&params, args)?;
+            if let Some(Token::Tuple(fields)) = tokens.first() {
+                if let Some(Token::Bytes(path)) = fields.first() {
+                    if path.len() >= 20 {
+                        let token = &path[path.len() - 20..];
+                        return Ok(format!("0x{}", hex::encode(token)));
                     }
                 }
             }
-            _ => {}
+            Err(anyhow!("Could not extract token address"))
+        }
+        // 1inch V5 swap(address executor, SwapDescription desc, bytes permit,
+        // bytes data) where desc = (srcToken, dstToken, srcReceiver,
+        // dstReceiver, amount, minReturnAmount, flags). dstToken is the buy.
+        "12aa3caf" => {
+            let params = [
+                ParamType::Address,
+                ParamType::Tuple(vec![
+                    ParamType::Address,
+                    ParamType::Address,
+                    ParamType::Address,
+                    ParamType::Address,
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                ]),
+                ParamType::Bytes,
+                ParamType::Bytes,
+            ];
+            let tokens = decode(&params, args)?;
+            if let Some(Token::Tuple(desc)) = tokens.get(1) {
+                if let Some(Token::Address(addr)) = desc.get(1) {
+                    return Ok(format!("{:?}", addr));
+                }
+            }
+            Err(anyhow!("Could not extract token address"))
+        }
+        // 0x transformERC20(inputToken, outputToken, inputAmount, minOutput,
+        // transformations). outputToken is the token being bought.
+        "415565b0" => {
+            let params = [
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Array(Box::new(ParamType::Tuple(vec![
+                    ParamType::Uint(32),
+                    ParamType::Bytes,
+                ]))),
+            ];
+            let tokens = decode(&params, args)?;
+            if let Some(Token::Address(addr)) = tokens.get(1) {
+                return Ok(format!("{:?}", addr));
+            }
+            Err(anyhow!("Could not extract token address"))
+        }
+        _ => Err(anyhow!("Could not extract token address")),
+    }
+}
+
+/// Fractional-Kelly position size for a trade mirroring `wallet`. The full
+/// Kelly fraction `f* = W - (1 - W) / R` comes from the wallet's win rate `W`
+/// and average multiplier `R` (the payoff ratio against the ~1x downside on a
+/// losing snipe), scaled by `kelly_fraction` (half-Kelly by default, to guard
+/// against `W`/`R` being noisy estimates) and capped at `max_position_pct`.
+/// The ML processor's `recommended_position_size` is blended in as a second,
+/// independent cap. A non-positive `f*` means the wallet's edge looks
+/// negative, so we fall back to `min_position_pct` rather than sizing to zero.
+/// The resulting fraction is applied against `current_capital` — the
+/// engine's live balance — so sizing tracks the real account as it grows or
+/// draws down, rather than a fixed notional.
+fn calculate_position_size(
+    wallet: &AlphaWallet,
+    ml: &RustMLProcessor,
+    kelly_fraction: f64,
+    max_position_pct: f64,
+    min_position_pct: f64,
+    current_capital: crate::money::Amount,
+) -> crate::money::Amount {
+    let w = wallet.win_rate.clamp(0.0, 1.0);
+    let r = wallet.avg_multiplier;
+    let kelly_star = if r > 0.0 { w - (1.0 - w) / r } else { 0.0 };
+
+    let kelly_sized = if kelly_star > 0.0 {
+        (kelly_star * kelly_fraction).min(max_position_pct)
+    } else {
+        min_position_pct
+    };
+
+    let ml_prediction = ml.predict_trade_outcome(&wallet.address, &HashMap::new());
+    let fraction = kelly_sized.min(ml_prediction.recommended_position_size).max(0.0);
+
+    tracing::info!(
+        "Position size for {}: kelly_f*={:.4} kelly_sized={:.4} ml_cap={:.4} -> fraction={:.4}",
+        wallet.address,
+        kelly_star,
+        kelly_sized,
+        ml_prediction.recommended_position_size,
+        fraction
+    );
+
+    crate::money::Amount::ether_from_f64(current_capital.to_f64() * fraction)
+}
+
+/// `ETHEREUM_RPC_URLS` (comma-separated) configures a failover set; falls
+/// back to the single `ETHEREUM_RPC_URL` for compatibility with existing
+/// single-endpoint deployments.
+fn rpc_urls_from_env() -> Vec<String> {
+    if let Ok(urls) = std::env::var("ETHEREUM_RPC_URLS") {
+        return urls.split(',').map(str::trim).filter(|u| !u.is_empty()).map(String::from).collect();
+    }
+    vec![std::env::var("ETHEREUM_RPC_URL").unwrap_or_default()]
+}
+
+/// Derive EIP-1559 fees from `eth_feeHistory`: the priority tip is the
+/// 75th-percentile reward over the last few blocks, and the fee cap is
+/// `2 * baseFee + tip` for headroom against a base-fee rise. Returns `None` on
+/// any RPC/parse error so the caller can fall back to the legacy gas price.
+/// Returns `(max_fee_per_gas, max_priority_fee_per_gas)` — callers must not
+/// collapse these into a single value, or the tip ends up equal to the full
+/// fee cap.
+async fn fee_history_gas_price(rpc_provider: &crate::web3_client::FailoverProvider) -> Option<(u64, u64)> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_feeHistory",
+        "params": ["0x5", "latest", [75]],
+    });
+
+    let response: Value = rpc_provider.post_raw(&request).await.ok()?;
+    let result = response.get("result")?;
+
+    let base_fees = result["baseFeePerGas"].as_array()?;
+    let base_fee = u128::from_str_radix(base_fees.last()?.as_str()?.trim_start_matches("0x"), 16).ok()?;
+
+    let rewards = result["reward"].as_array()?;
+    let (sum, count) = rewards.iter().filter_map(|row| {
+        let tip = row.as_array()?.first()?.as_str()?;
+        u128::from_str_radix(tip.trim_start_matches("0x"), 16).ok()
+    }).fold((0u128, 0u128), |(sum, count), tip| (sum + tip, count + 1));
+    let tip = if count > 0 { sum / count } else { 0 };
+
+    Some(((base_fee * 2 + tip) as u64, tip as u64))
+}
+
+/// Light-client-style head-verification gate. Three independent checks must
+/// all pass before a trade is allowed to spend capital:
+///
+/// 1. Freshness: the primary RPC's latest block timestamp is within two
+///    minutes of now.
+/// 2. Parent-hash linkage: if this scanner verified an earlier head, the
+///    current head's `parent_hash` must chain back to it (directly, or via
+///    the block-number gap being consistent with blocks we haven't seen yet).
+///    A mismatch means our own view of the chain was reorged out from under
+///    us between checks, so the trade is rejected.
+/// 3. Cross-client agreement: when `LIGHT_CLIENT_RPC_URL` is configured, its
+///    reported head number must be within one block of the primary's.
+///
+/// This is a practical approximation of a true light client, not one: we
+/// don't run a beacon-chain sync-committee verifier, so we can't check
+/// consensus signatures over the header. What we *can* check cheaply — that
+/// our own successive observations of the head form an unbroken hash chain,
+/// and that a second independent node agrees — is what's implemented here.
+async fn verify_chain_head(
+    rpc_ws: &Provider<Ws>,
+    client: &reqwest::Client,
+    last_verified_head: &Mutex<Option<(u64, EthersH256)>>,
+) -> bool {
+    let primary = match primary_head(rpc_ws).await {
+        Some(head) => head,
+        None => return false,
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if now.saturating_sub(primary.timestamp) > 120 {
+        return false; // Head too old; our node is likely behind.
+    }
+
+    {
+        let mut last = last_verified_head.lock();
+        if let Some((last_number, last_hash)) = *last {
+            // Only enforce linkage across a single-block step; a larger gap
+            // (we skipped some blocks between checks) can't be verified
+            // against a head we never observed.
+            if primary.number == last_number + 1 && primary.parent_hash != last_hash {
+                tracing::warn!(
+                    "chain head reorg detected: block {} parent {:?} != last verified hash {:?}",
+                    primary.number, primary.parent_hash, last_hash
+                );
+                return false;
+            }
+        }
+        *last = Some((primary.number, primary.hash));
+    }
+
+    if let Ok(light_url) = std::env::var("LIGHT_CLIENT_RPC_URL") {
+        match fetch_head(client, &light_url).await {
+            Some(light) => return primary.number.abs_diff(light.0) <= 1,
+            None => return false,
         }
-        
-        Err(anyhow!("Could not extract token address"))
     }
+
+    true
+}
+
+struct PrimaryHead {
+    number: u64,
+    hash: EthersH256,
+    parent_hash: EthersH256,
+    timestamp: u64,
+}
+
+/// Return the latest block's number, hash, and parent hash over the shared
+/// ethers-rs WebSocket provider, or `None` on any RPC error.
+async fn primary_head(rpc_ws: &Provider<Ws>) -> Option<PrimaryHead> {
+    let block = rpc_ws.get_block(ethers::types::BlockNumber::Latest).await.ok()??;
+    Some(PrimaryHead {
+        number: block.number?.as_u64(),
+        hash: block.hash?,
+        parent_hash: block.parent_hash,
+        timestamp: block.timestamp.as_u64(),
+    })
+}
+
+/// Return `(block_number, block_timestamp)` for the latest block, or `None` on
+/// any RPC/parse error.
+async fn fetch_head(client: &reqwest::Client, rpc_url: &str) -> Option<(u64, u64)> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_getBlockByNumber",
+        "params": ["latest", false],
+    });
+
+    let response: Value = client.post(rpc_url).json(&request).send().await.ok()?.json().await.ok()?;
+    let block = response.get("result")?;
+    let number = u64::from_str_radix(block["number"].as_str()?.trim_start_matches("0x"), 16).ok()?;
+    let timestamp = u64::from_str_radix(block["timestamp"].as_str()?.trim_start_matches("0x"), 16).ok()?;
+    Some((number, timestamp))
+}
+
+/// Pull the output token (last element) from a decoded `address[] path` token.
+fn last_address_of_path(token: Option<&Token>) -> Result<String> {
+    if let Some(Token::Array(path)) = token {
+        if let Some(Token::Address(addr)) = path.last() {
+            return Ok(format!("{:?}", addr));
+        }
+    }
+    Err(anyhow!("Could not extract token address"))
+}
+
+/// Effective gas price of a pending transaction, handling both legacy and
+/// EIP-1559 typed transactions. Legacy txs carry `gas_price`; type-2 txs leave
+/// it empty and instead expose a `max_fee_per_gas` cap, which is the most the
+/// sender is willing to pay and therefore the figure we must at least match to
+/// out-bid them.
+fn effective_gas_price(tx: &Transaction) -> u64 {
+    if let Some(gas_price) = tx.gas_price {
+        return gas_price.as_u64();
+    }
+    tx.max_fee_per_gas.unwrap_or_default().as_u64()
 }
\ No newline at end of file
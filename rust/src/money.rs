@@ -0,0 +1,106 @@
+use anyhow::{anyhow, Result};
+use ethers::types::U256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Minimum position size expressed in wei (0.01 ETH), used by `execute_buy` so
+/// the dust check is an integer comparison rather than a float threshold.
+pub const MIN_POSITION_WEI: u128 = 10_000_000_000_000_000; // 0.01 * 1e18
+
+/// Fixed-precision on-chain amount: a 256-bit unsigned integer of base units
+/// plus the token's decimal count. Arithmetic stays in integer space so sizing
+/// near dust thresholds is exact, unlike the previous `f64` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Amount {
+    #[doc = "Value in the token's smallest unit (wei for 18-decimal tokens)."]
+    pub raw: U256,
+    pub decimals: u8,
+}
+
+impl Amount {
+    pub fn new(raw: U256, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// An 18-decimal (ETH/WETH) amount from a raw wei value.
+    pub fn from_wei(raw: U256) -> Self {
+        Self { raw, decimals: 18 }
+    }
+
+    pub fn zero() -> Self {
+        Self { raw: U256::zero(), decimals: 18 }
+    }
+
+    /// Convert a human ETH figure into wei. Kept for ingesting config and
+    /// legacy float inputs; trading math never routes back through `f64`.
+    pub fn ether_from_f64(eth: f64) -> Self {
+        let wei = (eth * 1e18).round() as u128;
+        Self::from_wei(U256::from(wei))
+    }
+
+    /// Lossy view for display/logging only.
+    pub fn to_f64(self) -> f64 {
+        let divisor = 10f64.powi(self.decimals as i32);
+        // U256 -> f64 via u128 is fine for display-scale magnitudes.
+        let units = (self.raw % U256::exp10(self.decimals as usize)).as_u128() as f64;
+        let whole = (self.raw / U256::exp10(self.decimals as usize)).as_u128() as f64;
+        whole + units / divisor
+    }
+
+    /// Scale this amount by the integer-scaled ratio `numerator / denominator`,
+    /// i.e. `raw * numerator / denominator`, avoiding float division. Used to
+    /// compute current value as `amount * current_price / entry_price`.
+    pub fn scale_ratio(self, numerator: U256, denominator: U256) -> Result<Self> {
+        if denominator.is_zero() {
+            return Err(anyhow!("division by zero in amount scaling"));
+        }
+        Ok(Self { raw: self.raw * numerator / denominator, decimals: self.decimals })
+    }
+
+    pub fn is_dust(self) -> bool {
+        self.raw < U256::from(MIN_POSITION_WEI)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // (value, decimals) as a pair so JSON round-trips losslessly.
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("Amount", 2)?;
+        s.serialize_field("raw", &self.raw.to_string())?;
+        s.serialize_field("decimals", &self.decimals)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(with = "hex_or_decimal_u256")]
+            raw: U256,
+            decimals: u8,
+        }
+        let r = Raw::deserialize(deserializer)?;
+        Ok(Self { raw: r.raw, decimals: r.decimals })
+    }
+}
+
+/// Serde adapter that accepts a U256 encoded as either a `0x…` hex string or a
+/// plain decimal string, and always serializes as decimal. Use with
+/// `#[serde(with = "hex_or_decimal_u256")]`.
+pub mod hex_or_decimal_u256 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if let Some(hex) = s.strip_prefix("0x") {
+            U256::from_str_radix(hex, 16).map_err(serde::de::Error::custom)
+        } else {
+            U256::from_dec_str(&s).map_err(serde::de::Error::custom)
+        }
+    }
+}
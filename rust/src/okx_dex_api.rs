@@ -18,9 +18,14 @@ pub struct SimulationResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeParams {
     pub token_address: String,
-    pub amount_in: f64,
+    /// Input amount in base units (wei), fixed-precision to avoid dust-level
+    /// rounding error.
+    pub amount_in: crate::money::Amount,
     pub slippage_tolerance: f64,
-    pub gas_tip: u64,
+    /// Dynamic EIP-1559 fee cap (wei per gas) from the gas oracle, replacing the
+    /// old static `gas_tip` constant.
+    pub max_fee_per_gas: u64,
+    pub max_priority_fee_per_gas: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,12 +47,19 @@ pub struct OkxClient {
 
 impl OkxClient {
     pub async fn new() -> Result<Self> {
-        let api_key = std::env::var("OKX_API_KEY")
-            .map_err(|_| anyhow!("OKX_API_KEY not set"))?;
-        let secret_key = std::env::var("OKX_SECRET_KEY")
-            .map_err(|_| anyhow!("OKX_SECRET_KEY not set"))?;
-        let passphrase = std::env::var("OKX_PASSPHRASE")
-            .map_err(|_| anyhow!("OKX_PASSPHRASE not set"))?;
+        // Prefer the encrypted keystore; fall back to the raw env vars so local
+        // development without a keystore keeps working.
+        let (api_key, secret_key, passphrase) = match crate::keystore::Keystore::from_env() {
+            Ok(keystore) => {
+                let s = keystore.secrets();
+                (s.okx_api_key.clone(), s.okx_secret_key.clone(), s.okx_passphrase.clone())
+            }
+            Err(_) => (
+                std::env::var("OKX_API_KEY").map_err(|_| anyhow!("OKX_API_KEY not set"))?,
+                std::env::var("OKX_SECRET_KEY").map_err(|_| anyhow!("OKX_SECRET_KEY not set"))?,
+                std::env::var("OKX_PASSPHRASE").map_err(|_| anyhow!("OKX_PASSPHRASE not set"))?,
+            ),
+        };
 
         Ok(Self {
             client: Client::builder()
@@ -155,11 +167,12 @@ impl OkxClient {
             "chainId": "1",
             "fromTokenAddress": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
             "toTokenAddress": params.token_address,
-            "amount": (params.amount_in * 1e18).to_string(),
+            "amount": params.amount_in.raw.to_string(),
             "slippage": params.slippage_tolerance.to_string(),
             "userWalletAddress": wallet_address,
             "referrer": "mimic_bot",
-            "gasPrice": params.gas_tip.to_string()
+            "maxFeePerGas": params.max_fee_per_gas.to_string(),
+            "maxPriorityFeePerGas": params.max_priority_fee_per_gas.to_string()
         });
 
         let headers = self.create_headers("POST", path, &body.to_string())?;
@@ -182,7 +195,7 @@ impl OkxClient {
                         .unwrap_or("0")
                         .parse()
                         .unwrap_or(0),
-                    effective_price: params.amount_in / swap_data["toTokenAmount"]
+                    effective_price: params.amount_in.to_f64() / swap_data["toTokenAmount"]
                         .as_str()
                         .unwrap_or("1")
                         .parse::<f64>()
@@ -232,6 +245,31 @@ impl OkxClient {
         Ok(0.0)
     }
 
+    /// Look up the current status of a previously submitted swap by its
+    /// on-chain transaction hash, for the control server's `get_open_orders`.
+    pub async fn get_order_status(&self, tx_hash: &str) -> Result<Value> {
+        let path = "/api/v5/dex/order-status";
+        let params = format!("txHash={}", tx_hash);
+        let url = format!("{}{}?{}", self.base_url, path, params);
+
+        let headers = self.create_headers("GET", path, "")?;
+        let response = self.client
+            .get(&url)
+            .headers(headers)
+            .send()
+            .await?;
+
+        let data: Value = response.json().await?;
+
+        if data["code"].as_str() == Some("0") {
+            if let Some(order) = data["data"].as_array().and_then(|arr| arr.first()) {
+                return Ok(order.clone());
+            }
+        }
+
+        Ok(json!({ "txHash": tx_hash, "status": "unknown" }))
+    }
+
     fn create_headers(&self, method: &str, path: &str, body: &str) -> Result<reqwest::header::HeaderMap> {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)?
@@ -0,0 +1,471 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::abi::{encode, Token};
+use ethers::middleware::gas_oracle::{GasOracleMiddleware, ProviderOracle};
+use ethers::middleware::{Middleware, NonceManagerMiddleware, SignerMiddleware};
+use ethers::prelude::*;
+use ethers::providers::{Http, Provider};
+use ethers::signers::{HDPath, Ledger, LocalWallet, Signer};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, U256};
+use serde_json::{json, Value};
+
+use crate::execution_engine::TradeSigner;
+use crate::gas::{GasOracle, GasStrategy};
+use crate::okx_dex_api::{ExecutionResult, OkxClient, TradeParams};
+
+/// Mainnet WETH, used to build the Uniswap V2 swap path directly. The router
+/// address itself isn't hardcoded here -- it's already a per-deployment field
+/// on `OnchainExecutor`.
+const WETH_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+/// `swapExactETHForTokens(uint256,address[],address,uint256)` selector.
+const SWAP_EXACT_ETH_FOR_TOKENS_SELECTOR: [u8; 4] = [0x7f, 0xf3, 0x6a, 0xb5];
+/// `swapExactTokensForETH(uint256,uint256,address[],address,uint256)` selector.
+const SWAP_EXACT_TOKENS_FOR_ETH_SELECTOR: [u8; 4] = [0x18, 0xcb, 0xaf, 0xe5];
+/// `approve(address,uint256)` selector.
+const ERC20_APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+/// `allowance(address,address)` selector.
+const ERC20_ALLOWANCE_SELECTOR: [u8; 4] = [0xdd, 0x62, 0xed, 0x3e];
+
+/// Common interface over the custodial OKX swap endpoint and this
+/// self-custody on-chain path, so a caller can use either without caring
+/// which it's talking to. `AlphaMirror` in `main` holds no `Executor`.
+/// `TradeSigner` (in `execution_engine`) only covers the buy side
+/// needed for mirroring; `Executor` adds the matching sell so a full
+/// self-custody round trip doesn't need OKX at all.
+#[async_trait]
+pub trait Executor: Send + Sync {
+    async fn execute_buy(&self, params: TradeParams) -> Result<ExecutionResult>;
+    async fn execute_sell(&self, params: TradeParams) -> Result<ExecutionResult>;
+}
+
+/// How a signed transaction reaches the network. `Public` broadcasts through
+/// the node's mempool like any other transaction; `PrivateBundle` packages it
+/// as a signed Flashbots-style bundle and submits it directly to a
+/// block-builder relay, hiding it from public searchers until it's included.
+#[derive(Debug, Clone)]
+pub enum SubmissionMode {
+    Public,
+    PrivateBundle {
+        /// Relay endpoint accepting `eth_sendBundle` / `flashbots_getBundleStatsV2`.
+        relay_url: String,
+        /// How many consecutive blocks to target before giving up, resubmitting
+        /// for the next block on each miss.
+        max_blocks: u64,
+        /// Whether the relay may still include the bundle if this tx reverts.
+        allow_revert: bool,
+    },
+}
+
+impl Default for SubmissionMode {
+    /// Opt-in only: public broadcast is unchanged unless a caller explicitly
+    /// switches to `PrivateBundle`.
+    fn default() -> Self {
+        SubmissionMode::Public
+    }
+}
+
+/// Fully-stacked middleware used to submit mirror trades on-chain, parameterised
+/// over the signer `S`: `SignerMiddleware` wraps a `NonceManagerMiddleware`
+/// wrapping a `GasOracleMiddleware`, so every transaction is signed, gets a
+/// locally tracked nonce, and is priced from the node's gas oracle. `S` is
+/// either a [`LocalWallet`] (keystore) or a [`Ledger`] hardware wallet.
+pub type ExecutorClient<S> =
+    SignerMiddleware<NonceManagerMiddleware<GasOracleMiddleware<Provider<Http>, ProviderOracle<Provider<Http>>>>, S>;
+
+/// Submits swaps through a DEX router using a self-custodied signer.
+pub struct OnchainExecutor<S: Signer> {
+    client: Arc<ExecutorClient<S>>,
+    router: Address,
+    /// Raw provider handle used for fee/access-list lookups ahead of
+    /// submission; the signing/nonce/gas middleware stack above wraps the
+    /// same connection.
+    provider: Provider<Http>,
+    gas_strategy: GasStrategy,
+    submission_mode: SubmissionMode,
+    /// Plain HTTP client used for relay requests, kept separate from the RPC
+    /// provider since it talks to an entirely different endpoint.
+    relay_client: reqwest::Client,
+}
+
+impl OnchainExecutor<LocalWallet> {
+    /// Build the executor from the HTTP RPC URL and the signing key held in the
+    /// encrypted keystore, stacking the signer, nonce manager, and gas oracle.
+    pub async fn new(router: Address) -> Result<Self> {
+        let keystore = crate::keystore::Keystore::from_env()?;
+        let signing_key = keystore
+            .secrets()
+            .signing_key
+            .clone()
+            .ok_or_else(|| anyhow!("no signing key in keystore"))?;
+        let wallet: LocalWallet = signing_key.parse()?;
+        Self::with_signer(router, wallet).await
+    }
+}
+
+impl OnchainExecutor<Ledger> {
+    /// Build the executor backed by a Ledger hardware wallet at the given BIP-44
+    /// account index, so the signing key never leaves the device.
+    pub async fn new_ledger(router: Address, account_index: usize) -> Result<Self> {
+        let http_url = std::env::var("ETH_HTTP_URL")
+            .map_err(|_| anyhow!("ETH_HTTP_URL not set"))?;
+        let provider = Provider::<Http>::try_from(&http_url)?;
+        let chain_id = provider.get_chainid().await?.as_u64();
+
+        let ledger = Ledger::new(HDPath::LedgerLive(account_index), chain_id).await?;
+        Self::with_signer(router, ledger).await
+    }
+}
+
+impl<S: Signer + 'static> OnchainExecutor<S> {
+    /// Stack the shared provider middleware (gas oracle + nonce manager) under
+    /// an arbitrary signer.
+    async fn with_signer(router: Address, signer: S) -> Result<Self> {
+        let http_url = std::env::var("ETH_HTTP_URL")
+            .map_err(|_| anyhow!("ETH_HTTP_URL not set"))?;
+        let provider = Provider::<Http>::try_from(&http_url)?;
+        let chain_id = provider.get_chainid().await?.as_u64();
+        let signer = signer.with_chain_id(chain_id);
+        let address = signer.address();
+
+        let oracle = ProviderOracle::new(provider.clone());
+        let with_gas = GasOracleMiddleware::new(provider.clone(), oracle);
+        let with_nonce = NonceManagerMiddleware::new(with_gas, address);
+        let client = SignerMiddleware::new(with_nonce, signer);
+
+        Ok(Self {
+            client: Arc::new(client),
+            router,
+            provider,
+            gas_strategy: GasStrategy::default(),
+            submission_mode: SubmissionMode::default(),
+            relay_client: reqwest::Client::new(),
+        })
+    }
+
+    /// Override the default per-chain gas strategy (e.g. `Legacy` for chains
+    /// without EIP-1559).
+    pub fn with_gas_strategy(mut self, gas_strategy: GasStrategy) -> Self {
+        self.gas_strategy = gas_strategy;
+        self
+    }
+
+    /// Override the default public-mempool submission with e.g.
+    /// `SubmissionMode::PrivateBundle` to avoid being sandwiched on mirrored
+    /// buys.
+    pub fn with_submission_mode(mut self, submission_mode: SubmissionMode) -> Self {
+        self.submission_mode = submission_mode;
+        self
+    }
+
+    pub fn address(&self) -> Address {
+        self.client.address()
+    }
+
+    /// Send pre-encoded router calldata with `value` wei attached and wait for
+    /// the transaction hash. Fees are priced per the configured
+    /// [`GasStrategy`] and, for EIP-1559 chains, the router/token storage
+    /// slots are prefetched via `eth_createAccessList` and attached as an
+    /// EIP-2930 access list before signing, trimming cold-access gas costs.
+    pub async fn submit_swap(&self, calldata: Bytes, value: U256) -> Result<TxHash> {
+        self.submit_calldata(self.router, calldata, value).await
+    }
+
+    /// Same as [`submit_swap`](Self::submit_swap) but against an arbitrary
+    /// destination rather than always the router -- used for the ERC-20
+    /// `approve` that [`Executor::execute_sell`] submits ahead of the swap
+    /// itself.
+    async fn submit_calldata(&self, to: Address, calldata: Bytes, value: U256) -> Result<TxHash> {
+        let oracle = GasOracle::new(self.provider.clone());
+        let fees = oracle.estimate_for_strategy(self.gas_strategy).await?;
+
+        let mut tx: TypedTransaction = match self.gas_strategy {
+            GasStrategy::Legacy => TransactionRequest::new()
+                .to(to)
+                .value(value)
+                .data(calldata)
+                .gas_price(fees.as_legacy_gas_price())
+                .into(),
+            GasStrategy::Eip1559 { .. } => Eip1559TransactionRequest::new()
+                .to(to)
+                .value(value)
+                .data(calldata)
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+                .into(),
+        };
+
+        if matches!(self.gas_strategy, GasStrategy::Eip1559 { .. }) {
+            let access_list = oracle.prefetch_access_list(&tx).await;
+            tx.set_access_list(access_list);
+        }
+
+        match &self.submission_mode {
+            SubmissionMode::Public => {
+                let pending = self.client.send_transaction(tx, None).await?;
+                Ok(pending.tx_hash())
+            }
+            SubmissionMode::PrivateBundle { relay_url, max_blocks, allow_revert } => {
+                self.submit_private_bundle(tx, relay_url, *max_blocks, *allow_revert).await
+            }
+        }
+    }
+
+    /// Sign `tx` locally and submit it as a single-transaction bundle to a
+    /// block-builder relay, targeting the next block and resubmitting for each
+    /// subsequent block (up to `max_blocks`) until it's confirmed included.
+    /// Never touches the public mempool, so there's nothing for a sandwich bot
+    /// to see ahead of inclusion.
+    async fn submit_private_bundle(
+        &self,
+        mut tx: TypedTransaction,
+        relay_url: &str,
+        max_blocks: u64,
+        allow_revert: bool,
+    ) -> Result<TxHash> {
+        self.client.fill_transaction(&mut tx, None).await?;
+        let signature = self
+            .client
+            .signer()
+            .sign_transaction(&tx)
+            .await
+            .map_err(|e| anyhow!("failed to sign bundle transaction: {e}"))?;
+        let raw_tx = tx.rlp_signed(&signature);
+        let tx_hash = TxHash::from(ethers::utils::keccak256(&raw_tx));
+
+        let mut target_block = self.provider.get_block_number().await?.as_u64() + 1;
+        for attempt in 1..=max_blocks.max(1) {
+            let bundle_hash = self.send_bundle(relay_url, &raw_tx, target_block, allow_revert, tx_hash).await?;
+
+            if self.poll_bundle_included(relay_url, &bundle_hash, target_block).await? {
+                tracing::info!("private bundle {} included in block {}", bundle_hash, target_block);
+                return Ok(tx_hash);
+            }
+
+            tracing::warn!(
+                "private bundle {} missed block {} (attempt {}/{}), resubmitting for next block",
+                bundle_hash,
+                target_block,
+                attempt,
+                max_blocks
+            );
+            target_block += 1;
+        }
+
+        Err(anyhow!("private bundle not included within {} blocks", max_blocks))
+    }
+
+    /// POST a single-transaction `eth_sendBundle` to the relay, authenticated
+    /// with a Flashbots-style `X-Flashbots-Signature` header derived from this
+    /// executor's own signing key. Returns the relay's bundle hash.
+    async fn send_bundle(
+        &self,
+        relay_url: &str,
+        raw_tx: &Bytes,
+        target_block: u64,
+        allow_revert: bool,
+        tx_hash: TxHash,
+    ) -> Result<String> {
+        let mut params = json!({
+            "txs": [format!("0x{}", hex::encode(raw_tx))],
+            "blockNumber": format!("0x{:x}", target_block),
+        });
+        if allow_revert {
+            params["revertingTxHashes"] = json!([format!("{:?}", tx_hash)]);
+        }
+
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_sendBundle",
+            "params": [params],
+        });
+        let body_str = body.to_string();
+        let signature_header = self.flashbots_signature_header(&body_str).await?;
+
+        let response: Value = self
+            .relay_client
+            .post(relay_url)
+            .header("X-Flashbots-Signature", signature_header)
+            .header("Content-Type", "application/json")
+            .body(body_str)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        response["result"]["bundleHash"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("relay did not return a bundle hash: {response}"))
+    }
+
+    /// Poll `flashbots_getBundleStatsV2` for the bundle a few times, giving up
+    /// once the chain head passes `target_block` without an inclusion.
+    async fn poll_bundle_included(&self, relay_url: &str, bundle_hash: &str, target_block: u64) -> Result<bool> {
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+
+            let body = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "flashbots_getBundleStatsV2",
+                "params": [bundle_hash, format!("0x{:x}", target_block)],
+            });
+            let response: Value = self.relay_client.post(relay_url).json(&body).send().await?.json().await?;
+            if response["result"]["isIncluded"].as_bool().unwrap_or(false) {
+                return Ok(true);
+            }
+
+            if self.provider.get_block_number().await?.as_u64() > target_block {
+                return Ok(false);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Sign `keccak256(body)` with this executor's key (EIP-191 personal-sign,
+    /// per the Flashbots relay spec) and format it as `address:signature`.
+    async fn flashbots_signature_header(&self, body: &str) -> Result<String> {
+        let digest = format!("0x{}", hex::encode(ethers::utils::keccak256(body.as_bytes())));
+        let signature = self
+            .client
+            .signer()
+            .sign_message(digest.as_bytes())
+            .await
+            .map_err(|e| anyhow!("failed to sign bundle payload: {e}"))?;
+        Ok(format!("{:?}:0x{}", self.address(), signature))
+    }
+
+    /// Current ERC-20 allowance the router holds over our address for `token`,
+    /// read with a plain `eth_call` rather than a state-changing transaction.
+    async fn allowance(&self, token: Address) -> Result<U256> {
+        let encoded_args = encode(&[Token::Address(self.address()), Token::Address(self.router)]);
+        let mut calldata = ERC20_ALLOWANCE_SELECTOR.to_vec();
+        calldata.extend_from_slice(&encoded_args);
+
+        let tx: TypedTransaction = TransactionRequest::new().to(token).data(calldata).into();
+        let result = self.provider.call(&tx, None).await?;
+        Ok(U256::from_big_endian(&result))
+    }
+
+    /// Submit an ERC-20 `approve(router, amount)` against `token`. Doesn't
+    /// wait for a receipt before returning -- like `submit_swap`, it relies on
+    /// the nonce manager to serialize this ahead of the swap that follows, so
+    /// the approve is guaranteed to land first even though both are in
+    /// flight.
+    async fn approve_router(&self, token: Address, amount: U256) -> Result<()> {
+        let encoded_args = encode(&[Token::Address(self.router), Token::Uint(amount)]);
+        let mut calldata = ERC20_APPROVE_SELECTOR.to_vec();
+        calldata.extend_from_slice(&encoded_args);
+        self.submit_calldata(token, calldata.into(), U256::zero()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S: Signer + 'static> Executor for OnchainExecutor<S> {
+    /// Build and submit `swapExactETHForTokens` ourselves -- no OKX quote
+    /// involved here, so `amountOutMin` is floored at the slippage-tolerant
+    /// fraction of the ETH amount in, the same bound the risk layer already
+    /// sized the trade against.
+    async fn execute_buy(&self, params: TradeParams) -> Result<ExecutionResult> {
+        let token: Address = params.token_address.parse()?;
+        let weth: Address = WETH_ADDRESS.parse()?;
+        let amount_in = params.amount_in.raw;
+        let amount_out_min = amount_in
+            * U256::from(((1.0 - params.slippage_tolerance).max(0.0) * 10_000.0) as u64)
+            / U256::from(10_000);
+        let deadline = U256::from(now_secs() + 600);
+
+        let encoded_args = encode(&[
+            Token::Uint(amount_out_min),
+            Token::Array(vec![Token::Address(weth), Token::Address(token)]),
+            Token::Address(self.address()),
+            Token::Uint(deadline),
+        ]);
+        let mut calldata = SWAP_EXACT_ETH_FOR_TOKENS_SELECTOR.to_vec();
+        calldata.extend_from_slice(&encoded_args);
+
+        let tx_hash = self.submit_swap(calldata.into(), amount_in).await?;
+        Ok(ExecutionResult {
+            tx_hash: format!("{:?}", tx_hash),
+            status: "submitted".to_string(),
+            gas_used: 0,
+            effective_price: 0.0,
+            amount_out: 0.0,
+        })
+    }
+
+    /// Top up the router's allowance if needed, then submit
+    /// `swapExactTokensForETH`. `params.amount_in` is the token amount being
+    /// sold, not ETH.
+    async fn execute_sell(&self, params: TradeParams) -> Result<ExecutionResult> {
+        let token: Address = params.token_address.parse()?;
+        let weth: Address = WETH_ADDRESS.parse()?;
+        let amount_in = params.amount_in.raw;
+
+        if self.allowance(token).await? < amount_in {
+            self.approve_router(token, amount_in).await?;
+        }
+
+        let amount_out_min = amount_in
+            * U256::from(((1.0 - params.slippage_tolerance).max(0.0) * 10_000.0) as u64)
+            / U256::from(10_000);
+        let deadline = U256::from(now_secs() + 600);
+
+        let encoded_args = encode(&[
+            Token::Uint(amount_in),
+            Token::Uint(amount_out_min),
+            Token::Array(vec![Token::Address(token), Token::Address(weth)]),
+            Token::Address(self.address()),
+            Token::Uint(deadline),
+        ]);
+        let mut calldata = SWAP_EXACT_TOKENS_FOR_ETH_SELECTOR.to_vec();
+        calldata.extend_from_slice(&encoded_args);
+
+        let tx_hash = self.submit_swap(calldata.into(), U256::zero()).await?;
+        Ok(ExecutionResult {
+            tx_hash: format!("{:?}", tx_hash),
+            status: "submitted".to_string(),
+            gas_used: 0,
+            effective_price: 0.0,
+            amount_out: 0.0,
+        })
+    }
+}
+
+/// So `ExecutionEngine::with_signer` can take an `OnchainExecutor` as its
+/// selection point alongside `OkxClient`/`WalletConnectSigner` -- reuses the
+/// buy side of `Executor` rather than introducing a second, competing
+/// backend-selection mechanism.
+#[async_trait]
+impl<S: Signer + 'static> TradeSigner for OnchainExecutor<S> {
+    async fn execute_buy_order(&self, params: TradeParams) -> Result<ExecutionResult> {
+        self.execute_buy(params).await
+    }
+}
+
+#[async_trait]
+impl Executor for OkxClient {
+    async fn execute_buy(&self, params: TradeParams) -> Result<ExecutionResult> {
+        OkxClient::execute_buy_order(self, params).await
+    }
+
+    /// The OKX REST integration this bot talks to (`okx_dex_api.rs`) has no
+    /// sell/close endpoint -- a real implementation needs that endpoint added
+    /// first rather than a fabricated response here.
+    async fn execute_sell(&self, _params: TradeParams) -> Result<ExecutionResult> {
+        Err(anyhow!("OkxClient has no sell/close endpoint implemented"))
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Amount;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+/// A resting limit order. `price` is a 1e18-scaled ETH quote, matching the
+/// fixed-point convention used for position entry prices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitOrder {
+    pub id: u64,
+    pub token_address: String,
+    pub side: OrderSide,
+    #[serde(with = "crate::money::hex_or_decimal_u256")]
+    pub price: U256,
+    pub amount: Amount,
+    pub timestamp: u64,
+    /// Monotonic insertion sequence, used as the time component of price-time
+    /// priority so two orders at the same price fill oldest-first.
+    pub sequence: u64,
+}
+
+/// Book of resting limit orders keyed by token, with price-time priority. The
+/// book is pure state; the engine drives matching against live prices.
+#[derive(Default)]
+pub struct OrderBook {
+    orders: HashMap<String, Vec<LimitOrder>>,
+    next_id: u64,
+    seq: u64,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rest a new order and return its id.
+    pub fn place(
+        &mut self,
+        token_address: &str,
+        side: OrderSide,
+        price: U256,
+        amount: Amount,
+        timestamp: u64,
+    ) -> u64 {
+        self.next_id += 1;
+        self.seq += 1;
+        let order = LimitOrder {
+            id: self.next_id,
+            token_address: token_address.to_string(),
+            side,
+            price,
+            amount,
+            timestamp,
+            sequence: self.seq,
+        };
+        self.orders.entry(token_address.to_string()).or_default().push(order);
+        self.next_id
+    }
+
+    /// Cancel a resting order by id. Returns `true` if it was found.
+    pub fn cancel(&mut self, id: u64) -> bool {
+        for book in self.orders.values_mut() {
+            if let Some(pos) = book.iter().position(|o| o.id == id) {
+                book.remove(pos);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// All resting orders, newest book state, for display.
+    pub fn list(&self) -> Vec<LimitOrder> {
+        self.orders.values().flatten().cloned().collect()
+    }
+
+    /// Remove and return every order for `token_address` that the live price has
+    /// crossed — buys when the price falls to/through their limit, sells when it
+    /// rises to/through theirs — ordered by price-time priority.
+    pub fn take_crossed(&mut self, token_address: &str, price_scaled: U256) -> Vec<LimitOrder> {
+        let Some(book) = self.orders.get_mut(token_address) else {
+            return Vec::new();
+        };
+
+        let mut filled = Vec::new();
+        book.retain(|order| {
+            let crossed = match order.side {
+                OrderSide::Buy => price_scaled <= order.price,
+                OrderSide::Sell => price_scaled >= order.price,
+            };
+            if crossed {
+                filled.push(order.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if book.is_empty() {
+            self.orders.remove(token_address);
+        }
+
+        // Price-time priority: best price first (high for buys, low for sells),
+        // then oldest first on ties.
+        filled.sort_by(|a, b| match a.side {
+            OrderSide::Buy => b.price.cmp(&a.price).then(a.sequence.cmp(&b.sequence)),
+            OrderSide::Sell => a.price.cmp(&b.price).then(a.sequence.cmp(&b.sequence)),
+        });
+        filled
+    }
+}
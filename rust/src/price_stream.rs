@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::okx_dex_api::OkxClient;
+
+/// A single price update for a tracked token. Price is a plain `f64` ETH quote;
+/// the engine scales it into fixed-point before comparing against thresholds.
+#[derive(Debug, Clone)]
+pub struct PriceTick {
+    pub token_address: String,
+    pub price: f64,
+}
+
+/// Command sent to a running [`PriceStream`] to grow or shrink the live
+/// subscription set as positions open and close.
+enum SubCommand {
+    Add(String),
+    Remove(String),
+}
+
+/// Cloneable handle for mutating a live subscription from elsewhere (e.g.
+/// `ExecutionEngine::execute_buy` adds a token, `close_position` removes it).
+#[derive(Clone)]
+pub struct PriceSubscription {
+    cmd_tx: mpsc::UnboundedSender<SubCommand>,
+}
+
+impl PriceSubscription {
+    pub fn add(&self, token_address: &str) {
+        let _ = self.cmd_tx.send(SubCommand::Add(token_address.to_string()));
+    }
+
+    pub fn remove(&self, token_address: &str) {
+        let _ = self.cmd_tx.send(SubCommand::Remove(token_address.to_string()));
+    }
+}
+
+/// WebSocket price oracle: keeps a live subscription for the set of tokens in
+/// open positions and forwards incremental ticks to a channel the engine drains
+/// to evaluate stop-loss/take-profit the instant a threshold is crossed.
+///
+/// The socket auto-reconnects with capped exponential backoff, resubscribes the
+/// current token set on every reconnect, and falls back to HTTP polling through
+/// [`OkxClient`] whenever the socket is unavailable so exits never go blind.
+pub struct PriceStream {
+    ws_url: String,
+    okx_client: Arc<OkxClient>,
+    tick_tx: mpsc::UnboundedSender<PriceTick>,
+    tokens: Arc<Mutex<HashSet<String>>>,
+    cmd_rx: mpsc::UnboundedReceiver<SubCommand>,
+}
+
+impl PriceStream {
+    /// Create a stream and its subscription handle. Drive it by awaiting
+    /// [`run`](Self::run) on a spawned task.
+    pub fn new(
+        ws_url: String,
+        okx_client: Arc<OkxClient>,
+    ) -> (Self, PriceSubscription, mpsc::UnboundedReceiver<PriceTick>) {
+        let (tick_tx, tick_rx) = mpsc::unbounded_channel();
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let stream = Self {
+            ws_url,
+            okx_client,
+            tick_tx,
+            tokens: Arc::new(Mutex::new(HashSet::new())),
+            cmd_rx,
+        };
+        (stream, PriceSubscription { cmd_tx }, tick_rx)
+    }
+
+    /// Reconnect loop: stream ticks while the socket is healthy, and on any drop
+    /// do a single HTTP fallback poll of the current set before backing off and
+    /// reconnecting.
+    pub async fn run(mut self) {
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            let started = Instant::now();
+            match self.stream_once().await {
+                Ok(()) => tracing::warn!("price stream closed, reconnecting"),
+                Err(e) => tracing::warn!("price stream error: {e}, reconnecting"),
+            }
+
+            // Cover the reconnect gap with a one-shot HTTP poll.
+            self.http_fallback_round().await;
+
+            if started.elapsed() > Duration::from_secs(60) {
+                backoff = Duration::from_secs(1);
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_secs(30));
+        }
+    }
+
+    /// Hold one socket open: subscribe the current set, then multiplex incoming
+    /// ticks and subscription commands until the socket drops.
+    async fn stream_once(&mut self) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let current: Vec<String> = self.tokens.lock().iter().cloned().collect();
+        if !current.is_empty() {
+            write.send(Message::Text(subscribe_frame(&current).to_string())).await?;
+        }
+
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let Some(msg) = msg else { break };
+                    match msg? {
+                        Message::Text(text) => {
+                            if let Some(tick) = parse_tick(&text) {
+                                let _ = self.tick_tx.send(tick);
+                            }
+                        }
+                        Message::Close(_) => break,
+                        _ => {}
+                    }
+                }
+                cmd = self.cmd_rx.recv() => {
+                    match cmd {
+                        Some(SubCommand::Add(token)) => {
+                            if self.tokens.lock().insert(token.clone()) {
+                                write.send(Message::Text(subscribe_frame(&[token]).to_string())).await?;
+                            }
+                        }
+                        Some(SubCommand::Remove(token)) => {
+                            if self.tokens.lock().remove(&token) {
+                                write.send(Message::Text(unsubscribe_frame(&token).to_string())).await?;
+                            }
+                        }
+                        None => break, // handle dropped; nothing left to stream
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One pass of HTTP price polling over the tracked set, used while the socket
+    /// is down so threshold checks keep firing, just at polling latency.
+    async fn http_fallback_round(&self) {
+        let tokens: Vec<String> = self.tokens.lock().iter().cloned().collect();
+        for token in tokens {
+            if let Ok(price) = self.okx_client.get_token_price(&token).await {
+                let _ = self.tick_tx.send(PriceTick { token_address: token, price });
+            }
+        }
+    }
+}
+
+fn subscribe_frame(tokens: &[String]) -> Value {
+    json!({
+        "op": "subscribe",
+        "args": tokens.iter().map(|t| json!({ "channel": "dex-price", "tokenAddress": t })).collect::<Vec<_>>()
+    })
+}
+
+fn unsubscribe_frame(token: &str) -> Value {
+    json!({
+        "op": "unsubscribe",
+        "args": [{ "channel": "dex-price", "tokenAddress": token }]
+    })
+}
+
+/// Pull a `(tokenAddress, price)` tick out of a provider notification, ignoring
+/// subscribe acks and heartbeats.
+fn parse_tick(text: &str) -> Option<PriceTick> {
+    let data: Value = serde_json::from_str(text).ok()?;
+    let entry = data["data"].as_array().and_then(|a| a.first())?;
+    let token_address = entry["tokenAddress"].as_str()?.to_string();
+    let price = entry["price"].as_str().and_then(|p| p.parse().ok())?;
+    Some(PriceTick { token_address, price })
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+/// Live market inputs used to derive per-trade risk parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct MarketState {
+    /// Pool liquidity in quote units (ETH-equivalent), from
+    /// `OkxClient::get_token_liquidity`. `None` means the lookup failed (the
+    /// common case for a freshly-sniped token, or anything not quoted by
+    /// OKX) and must not be treated the same as `Some(0.0)` (a pool that
+    /// genuinely has no liquidity) — conflating the two zeroed out the
+    /// position size for every token liquidity couldn't be fetched for.
+    pub liquidity: Option<f64>,
+    /// Price volatility as a fraction (e.g. `0.4` = 40%).
+    /// `ExecutionEngine::execute_buy` fills it from
+    /// `RiskConfig::default_volatility` for every token; no price history
+    /// feeds it.
+    pub volatility: f64,
+}
+
+/// Resolved risk parameters for a single trade, computed by
+/// [`RiskConfig::params_for`].
+#[derive(Debug, Clone, Copy)]
+pub struct RiskParams {
+    /// Slippage tolerance as a fraction, passed to the swap.
+    pub slippage: f64,
+    /// Stop-loss as a fraction below entry.
+    pub stop_loss_pct: f64,
+    /// Take-profit as a multiple of entry (e.g. `5.0` = 5x).
+    pub take_profit_mult: f64,
+    /// Fraction of capital allowed in this position.
+    pub max_position_size: f64,
+}
+
+/// Per-token pins that bypass the dynamic computation for specific fields.
+#[derive(Debug, Clone, Default)]
+pub struct RiskOverride {
+    pub slippage: Option<f64>,
+    pub stop_loss_pct: Option<f64>,
+    pub take_profit_mult: Option<f64>,
+    pub max_position_size: Option<f64>,
+}
+
+/// Configurable layer that turns live liquidity and volatility into per-trade
+/// slippage, stop-loss, take-profit, and position sizing, replacing the old
+/// hardcoded constants. Thin pools get wider slippage and smaller size;
+/// stop-loss and take-profit scale with volatility.
+#[derive(Debug, Clone)]
+pub struct RiskConfig {
+    pub base_slippage: f64,
+    pub max_slippage: f64,
+    /// Liquidity at which a full-size position is allowed; below it, size and
+    /// slippage scale linearly with the shortfall.
+    pub reference_liquidity: f64,
+    pub stop_loss_vol_mult: f64,
+    pub take_profit_vol_mult: f64,
+    pub base_max_position_size: f64,
+    pub max_positions: usize,
+    /// Volatility `ExecutionEngine::execute_buy` passes to `params_for` for
+    /// every token; no per-token price history is consulted.
+    pub default_volatility: f64,
+    pub overrides: HashMap<String, RiskOverride>,
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            base_slippage: 0.05,
+            max_slippage: 0.30,
+            reference_liquidity: 250.0, // ETH-equivalent
+            stop_loss_vol_mult: 1.0,
+            take_profit_vol_mult: 10.0,
+            base_max_position_size: 0.30,
+            max_positions: 5,
+            default_volatility: 0.2,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl RiskConfig {
+    /// Compute trade parameters for `token_address` given live market state,
+    /// applying any per-token overrides last.
+    pub fn params_for(&self, token_address: &str, market: MarketState) -> RiskParams {
+        // Thinness in [0, 1]: 0 at/above reference liquidity, rising toward 1 as
+        // the pool shrinks. Unknown liquidity (lookup failed) is scored as if
+        // it were at the reference level rather than zero, so a trade isn't
+        // sized to nothing just because we couldn't fetch a quote; a pool we
+        // positively confirmed is empty still gets the full thinness penalty.
+        let thinness = match market.liquidity {
+            Some(liquidity) if liquidity > 0.0 => {
+                (1.0 - liquidity / self.reference_liquidity).clamp(0.0, 1.0)
+            }
+            Some(_) => 1.0,
+            None => 0.0,
+        };
+
+        let slippage = (self.base_slippage * (1.0 + thinness) + market.volatility * 0.5)
+            .clamp(self.base_slippage, self.max_slippage);
+
+        // Shrink size in thin pools; never exceed the configured base.
+        let max_position_size = (self.base_max_position_size * (1.0 - thinness))
+            .clamp(0.0, self.base_max_position_size);
+
+        // Stop/take as volatility multiples, floored so a calm token still has
+        // a sane band.
+        let stop_loss_pct = (market.volatility * self.stop_loss_vol_mult).clamp(0.05, 0.9);
+        let take_profit_mult = (1.0 + market.volatility * self.take_profit_vol_mult).max(1.5);
+
+        let mut params = RiskParams {
+            slippage,
+            stop_loss_pct,
+            take_profit_mult,
+            max_position_size,
+        };
+
+        if let Some(o) = self.overrides.get(token_address) {
+            if let Some(v) = o.slippage {
+                params.slippage = v;
+            }
+            if let Some(v) = o.stop_loss_pct {
+                params.stop_loss_pct = v;
+            }
+            if let Some(v) = o.take_profit_mult {
+                params.take_profit_mult = v;
+            }
+            if let Some(v) = o.max_position_size {
+                params.max_position_size = v;
+            }
+        }
+
+        params
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+/// Magic prefix identifying an encrypted snapshot file.
+const MAGIC: &[u8; 4] = b"MMSS";
+/// On-disk schema version. Bump when the encrypted payload layout changes so
+/// [`open`] can reject snapshots it can't interpret.
+pub const SCHEMA_VERSION: u16 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24; // XChaCha20 extended nonce
+
+/// Encrypt `plaintext` under a password, returning a self-describing blob of
+/// `MAGIC || version || salt || nonce || ciphertext`. The key is derived with
+/// Argon2id so a leaked snapshot isn't brute-forceable at GPU speed, and the
+/// payload is sealed with XChaCha20-Poly1305.
+pub fn seal(plaintext: &[u8], password: &str) -> Result<Vec<u8>> {
+    let mut rng = rand::thread_rng();
+
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(password, &salt)?;
+    let cipher = XChaCha20Poly1305::new((&key).into());
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), plaintext)
+        .map_err(|_| anyhow!("failed to encrypt snapshot"))?;
+
+    let mut blob = Vec::with_capacity(4 + 2 + SALT_LEN + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(MAGIC);
+    blob.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Decrypt a blob produced by [`seal`], validating the magic and schema header
+/// first. Returns the decrypted plaintext.
+pub fn open(blob: &[u8], password: &str) -> Result<Vec<u8>> {
+    let header_len = 4 + 2 + SALT_LEN + NONCE_LEN;
+    if blob.len() < header_len {
+        return Err(anyhow!("snapshot is truncated"));
+    }
+    if &blob[..4] != MAGIC {
+        return Err(anyhow!("not a recognised snapshot file"));
+    }
+    let version = u16::from_le_bytes([blob[4], blob[5]]);
+    if version != SCHEMA_VERSION {
+        return Err(anyhow!(
+            "unsupported snapshot version {version} (expected {SCHEMA_VERSION})"
+        ));
+    }
+
+    let salt = &blob[6..6 + SALT_LEN];
+    let nonce_bytes = &blob[6 + SALT_LEN..header_len];
+    let ciphertext = &blob[header_len..];
+
+    let key = derive_key(password, salt)?;
+    let cipher = XChaCha20Poly1305::new((&key).into());
+    cipher
+        .decrypt(XNonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| anyhow!("failed to decrypt snapshot (wrong password?)"))
+}
+
+/// Write `bytes` to `path` atomically: the data lands in a sibling temp file
+/// that is fsynced and then renamed over the target, so a crash mid-write can
+/// never leave a half-written snapshot behind.
+pub fn write_atomic(path: impl AsRef<Path>, bytes: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+
+    let mut file = std::fs::File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    use argon2::Argon2;
+
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("key derivation failed: {e}"))?;
+    Ok(key)
+}
@@ -0,0 +1,322 @@
+use std::sync::Arc;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use anyhow::{Result, anyhow};
+use ethers::contract::abigen;
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::{Http, Middleware, Provider, Ws};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{Address, U256};
+use ethers::utils::Anvil;
+use lru::LruCache;
+use parking_lot::Mutex;
+use reqwest::Client;
+
+use crate::okx_dex_api::OkxClient;
+
+/// Mainnet Uniswap V2 router and WETH, used as the venue for the fork
+/// buy/sell round-trip.
+const UNISWAP_V2_ROUTER: &str = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D";
+const WETH_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+/// WETH spent on the simulated buy (0.1 ETH).
+const SIM_BUY_WEI: u128 = 100_000_000_000_000_000;
+
+abigen!(
+    UniswapV2Router,
+    r#"[
+        function swapExactETHForTokensSupportingFeeOnTransferTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline) payable
+        function swapExactTokensForETHSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline)
+    ]"#,
+    Erc20,
+    r#"[
+        function balanceOf(address owner) view returns (uint256)
+        function approve(address spender, uint256 amount) returns (bool)
+        function owner() view returns (address)
+    ]"#,
+);
+
+pub struct TokenValidator {
+    okx_client: Arc<OkxClient>,
+    etherscan_client: Client,
+    cache: Arc<Mutex<LruCache<String, bool>>>,
+    blacklist: Arc<Mutex<Vec<String>>>,
+    /// Upstream RPC the in-process EVM forks from.
+    rpc_url: String,
+    /// Shared WebSocket provider used for push-based contract queries (e.g.
+    /// `owner()`), so on-chain reads don't fall back to per-call HTTP polling.
+    rpc_ws: Arc<Provider<Ws>>,
+    router: Address,
+    weth: Address,
+    /// Round-trip effective tax above which a token is treated as a honeypot.
+    tax_threshold: f64,
+}
+
+impl TokenValidator {
+    /// `rpc_ws` is the single WebSocket provider shared with
+    /// [`MempoolScanner`](crate::mempool_scanner::MempoolScanner). The
+    /// scanner's reconnect/backoff loop covers its own pending-tx subscription
+    /// socket, not this provider.
+    pub fn new(okx_client: Arc<OkxClient>, rpc_ws: Arc<Provider<Ws>>) -> Self {
+        let cache_size = NonZeroUsize::new(1000).unwrap();
+        Self {
+            okx_client,
+            etherscan_client: Client::new(),
+            cache: Arc::new(Mutex::new(LruCache::new(cache_size))),
+            blacklist: Arc::new(Mutex::new(Vec::new())),
+            rpc_url: std::env::var("ETHEREUM_RPC_URL").unwrap_or_default(),
+            rpc_ws,
+            router: UNISWAP_V2_ROUTER.parse().expect("valid router address"),
+            weth: WETH_ADDRESS.parse().expect("valid WETH address"),
+            tax_threshold: 0.30,
+        }
+    }
+
+    pub async fn validate_token(&self, token_address: &str) -> Result<bool> {
+        let addr_lower = token_address.to_lowercase();
+        
+        if let Some(&cached) = self.cache.lock().get(&addr_lower) {
+            return Ok(cached);
+        }
+
+        if self.is_blacklisted(&addr_lower) {
+            self.cache.lock().put(addr_lower, false);
+            return Ok(false);
+        }
+
+        let validation_result = self.perform_comprehensive_validation(&addr_lower).await?;
+        self.cache.lock().put(addr_lower, validation_result);
+        
+        Ok(validation_result)
+    }
+
+    async fn perform_comprehensive_validation(&self, token_address: &str) -> Result<bool> {
+        let validation_tasks = vec![
+            self.check_contract_verification(token_address),
+            self.check_liquidity_requirements(token_address),
+            self.check_ownership_renounced(token_address),
+            self.check_no_malicious_functions(token_address),
+            self.check_transfer_and_honeypot(token_address),
+        ];
+
+        let results = futures_util::future::join_all(validation_tasks).await;
+        
+        for result in results {
+            if !result? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn check_contract_verification(&self, token_address: &str) -> Result<bool> {
+        let etherscan_api_key = std::env::var("ETHERSCAN_API_KEY")
+            .map_err(|_| anyhow!("ETHERSCAN_API_KEY not set"))?;
+
+        let url = format!(
+            "https://api.etherscan.io/api?module=contract&action=getsourcecode&address={}&apikey={}",
+            token_address, etherscan_api_key
+        );
+
+        let response: Value = self.etherscan_client
+            .get(&url)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(result) = response["result"].as_array() {
+            if let Some(contract) = result.first() {
+                let source_code = contract["SourceCode"].as_str().unwrap_or("");
+                return Ok(!source_code.is_empty());
+            }
+        }
+
+        Ok(false)
+    }
+
+    async fn check_liquidity_requirements(&self, token_address: &str) -> Result<bool> {
+        let liquidity = self.okx_client.get_token_liquidity(token_address).await?;
+        Ok(liquidity >= 50000.0) // Minimum $50K liquidity
+    }
+
+    /// Queries `owner()` over the shared WebSocket provider instead of a
+    /// per-call HTTP client, so the check rides the same push-based connection
+    /// as the mempool scanner rather than opening its own polling transport.
+    async fn check_ownership_renounced(&self, token_address: &str) -> Result<bool> {
+        let address: Address = token_address.parse()?;
+        let contract = Erc20::new(address, self.rpc_ws.clone());
+
+        match contract.owner().call().await {
+            Ok(owner) => {
+                let zero_address = Address::zero();
+                let dead_address: Address = "0x000000000000000000000000000000000000dead".parse()?;
+
+                Ok(owner == zero_address || owner == dead_address)
+            }
+            Err(_) => Ok(true), // If no owner function, assume renounced
+        }
+    }
+
+    async fn check_no_malicious_functions(&self, token_address: &str) -> Result<bool> {
+        let etherscan_api_key = std::env::var("ETHERSCAN_API_KEY")
+            .map_err(|_| anyhow!("ETHERSCAN_API_KEY not set"))?;
+
+        let url = format!(
+            "https://api.etherscan.io/api?module=contract&action=getsourcecode&address={}&apikey={}",
+            token_address, etherscan_api_key
+        );
+
+        let response: Value = self.etherscan_client
+            .get(&url)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(result) = response["result"].as_array() {
+            if let Some(contract) = result.first() {
+                let source_code = contract["SourceCode"].as_str().unwrap_or("").to_lowercase();
+                
+                let dangerous_patterns = vec![
+                    "blacklist", "pause", "setfees", "cooldown", "antisell",
+                    "rebase", "mint(", "burn(", "onlyowner", "_transfer",
+                    "addliquidity", "removeliquidity", "settaxes", "setfee",
+                ];
+
+                for pattern in dangerous_patterns {
+                    if source_code.contains(pattern) {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Combines what used to be two separate checks (transfer test, honeypot
+    /// detection) into one: both answers fall out of the same fork
+    /// buy/sell round-trip, so running it twice just doubled the `Anvil::fork`
+    /// cost per token for no extra signal. The buy leg covers the transfer
+    /// test (token can't even be acquired) and the sell leg covers the
+    /// honeypot check (sell reverts, or tax exceeds [`tax_threshold`]).
+    async fn check_transfer_and_honeypot(&self, token_address: &str) -> Result<bool> {
+        self.simulate_round_trip(token_address).await
+    }
+
+    /// Spin up an in-process forked EVM at the latest block, sign with Anvil's
+    /// first pre-funded dev account, buy `token_address` for a small WETH amount, then sell
+    /// the full balance back to WETH. The token is a honeypot if the sell
+    /// reverts, if the buy yields a zero balance, or if the round-trip
+    /// effective tax `(eth_in - eth_out)/eth_in` exceeds [`tax_threshold`].
+    /// Returns `Ok(true)` when the token looks safe.
+    async fn simulate_round_trip(&self, token_address: &str) -> Result<bool> {
+        if self.rpc_url.is_empty() {
+            return Err(anyhow!("ETHEREUM_RPC_URL not set"));
+        }
+        let token: Address = token_address.parse()?;
+
+        let anvil = Anvil::new().fork(&self.rpc_url).spawn();
+        let provider = Provider::<Http>::try_from(anvil.endpoint())?;
+        let wallet: LocalWallet = anvil.keys()[0].clone().into();
+        let account = wallet.address();
+        let client = Arc::new(SignerMiddleware::new(
+            provider,
+            wallet.with_chain_id(anvil.chain_id()),
+        ));
+
+        let router = UniswapV2Router::new(self.router, client.clone());
+        let erc20 = Erc20::new(token, client.clone());
+        let deadline = U256::from(now_secs() + 600);
+
+        // Buy leg.
+        let eth_in = U256::from(SIM_BUY_WEI);
+        router
+            .swap_exact_eth_for_tokens_supporting_fee_on_transfer_tokens(
+                U256::zero(),
+                vec![self.weth, token],
+                account,
+                deadline,
+            )
+            .value(eth_in)
+            .send()
+            .await?
+            .await?;
+
+        let received = erc20.balance_of(account).call().await?;
+        if received.is_zero() {
+            return Ok(false); // nothing came back — unsellable by construction
+        }
+
+        // Sell leg: approve, then swap the whole balance back to WETH.
+        erc20.approve(self.router, received).send().await?.await?;
+        let eth_before = client.get_balance(account, None).await?;
+        let sell = router.swap_exact_tokens_for_eth_supporting_fee_on_transfer_tokens(
+            received,
+            U256::zero(),
+            vec![token, self.weth],
+            account,
+            deadline,
+        );
+
+        let receipt = match sell.send().await {
+            Ok(pending) => pending.await?,
+            Err(_) => return Ok(false), // sell reverts — classic honeypot
+        };
+        if let Some(receipt) = &receipt {
+            if let Some(gas) = receipt.gas_used {
+                tracing::debug!("fork sell gas for {}: {}", token_address, gas);
+            }
+        }
+
+        let eth_after = client.get_balance(account, None).await?;
+        let eth_out = eth_after.saturating_sub(eth_before);
+
+        // Effective tax over the round trip; wei-scaled ratio kept in f64 since
+        // the threshold is a fraction.
+        let tax = 1.0 - (eth_out.as_u128() as f64 / eth_in.as_u128() as f64);
+        if tax > self.tax_threshold {
+            tracing::warn!("{} round-trip tax {:.1}% exceeds threshold", token_address, tax * 100.0);
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    fn is_blacklisted(&self, token_address: &str) -> bool {
+        self.blacklist.lock().contains(&token_address.to_string())
+    }
+
+    pub fn add_to_blacklist(&self, token_address: String) {
+        self.blacklist.lock().push(token_address);
+    }
+
+    pub async fn load_rugdoc_blacklist(&self) -> Result<()> {
+        let url = "https://raw.githubusercontent.com/rugdoc/honeypot-list/main/addresses.json";
+        
+        match self.etherscan_client.get(url).send().await {
+            Ok(response) => {
+                if let Ok(addresses) = response.json::<Vec<String>>().await {
+                    let mut blacklist = self.blacklist.lock();
+                    for addr in addresses {
+                        blacklist.push(addr.to_lowercase());
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
\ No newline at end of file
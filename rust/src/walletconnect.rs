@@ -0,0 +1,351 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::abi::{encode, Token};
+use ethers::types::{Address, U256};
+use parking_lot::Mutex;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use futures_util::{SinkExt, StreamExt};
+
+use crate::execution_engine::TradeSigner;
+use crate::okx_dex_api::{ExecutionResult, TradeParams};
+
+/// Mainnet Uniswap V2 router and WETH, the venue `execute_buy_order` routes
+/// the wallet-signed swap through.
+const UNISWAP_V2_ROUTER: &str = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D";
+const WETH_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+/// `swapExactETHForTokens(uint256,address[],address,uint256)` selector.
+const SWAP_EXACT_ETH_FOR_TOKENS_SELECTOR: [u8; 4] = [0x7f, 0xf3, 0x6a, 0xb5];
+
+/// Session state persisted to disk so a restart can reconnect without
+/// re-pairing (re-scanning a QR every time the bot restarts isn't workable).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedSession {
+    pairing_topic: String,
+    sym_key: String,
+    session_topic: String,
+    account: String,
+    chain_id: u64,
+}
+
+/// Self-custody signing path over WalletConnect v2. Instead of handing the trade
+/// to the custodial OKX swap endpoint, we relay an `eth_sendTransaction` request
+/// to the operator's own wallet, which signs and broadcasts it; the private key
+/// never leaves the device.
+pub struct WalletConnectSigner {
+    relay_url: String,
+    project_id: String,
+    /// Topic the pairing URI advertises; a `wc_sessionPropose` sent here is
+    /// what the wallet scans the QR / opens the URI to see.
+    pairing_topic: String,
+    /// Symmetric key embedded in the pairing URI. A full WalletConnect client
+    /// uses this to encrypt pairing-topic traffic; our relay calls are plain
+    /// JSON-RPC (see `relay_request`), so it's carried for URI-compatibility
+    /// with wallets that parse it out, but doesn't double as transport crypto
+    /// here.
+    sym_key: String,
+    /// Populated once the wallet approves the session, either by
+    /// `ensure_session` or by restoring a persisted session.
+    session_topic: Mutex<Option<String>>,
+    account: Mutex<Option<String>>,
+    chain_id: u64,
+    request_id: AtomicU64,
+    session_path: PathBuf,
+}
+
+impl WalletConnectSigner {
+    /// Start a fresh pairing for `chain_id`. Call [`print_uri`](Self::print_uri)
+    /// to get the URI for the operator to scan, then
+    /// [`ensure_session`](Self::ensure_session) to block until the wallet
+    /// approves it.
+    pub fn pair(chain_id: u64) -> Result<Self> {
+        let project_id = std::env::var("WALLETCONNECT_PROJECT_ID")
+            .map_err(|_| anyhow!("WALLETCONNECT_PROJECT_ID not set"))?;
+
+        let mut rng = rand::thread_rng();
+        let mut topic_bytes = [0u8; 32];
+        rng.fill_bytes(&mut topic_bytes);
+        let mut sym_key_bytes = [0u8; 32];
+        rng.fill_bytes(&mut sym_key_bytes);
+
+        Ok(Self {
+            relay_url: "wss://relay.walletconnect.com".to_string(),
+            project_id,
+            pairing_topic: hex::encode(topic_bytes),
+            sym_key: hex::encode(sym_key_bytes),
+            session_topic: Mutex::new(None),
+            account: Mutex::new(None),
+            chain_id,
+            request_id: AtomicU64::new(1),
+            session_path: session_path_from_env(),
+        })
+    }
+
+    /// Restore a previously approved session from disk instead of pairing
+    /// again, for reconnects across a bot restart.
+    pub fn from_session_file() -> Result<Self> {
+        let project_id = std::env::var("WALLETCONNECT_PROJECT_ID")
+            .map_err(|_| anyhow!("WALLETCONNECT_PROJECT_ID not set"))?;
+        let path = session_path_from_env();
+        let data = std::fs::read_to_string(&path)
+            .map_err(|_| anyhow!("no persisted WalletConnect session at {}", path.display()))?;
+        let persisted: PersistedSession = serde_json::from_str(&data)?;
+
+        Ok(Self {
+            relay_url: "wss://relay.walletconnect.com".to_string(),
+            project_id,
+            pairing_topic: persisted.pairing_topic,
+            sym_key: persisted.sym_key,
+            session_topic: Mutex::new(Some(persisted.session_topic)),
+            account: Mutex::new(Some(persisted.account)),
+            chain_id: persisted.chain_id,
+            request_id: AtomicU64::new(1),
+            session_path: path,
+        })
+    }
+
+    /// The pairing URI the operator's wallet scans as a QR code (or opens
+    /// directly on mobile) to approve this session. Printed to stdout as a
+    /// convenience since we have no QR renderer of our own.
+    pub fn print_uri(&self) -> String {
+        let uri = format!(
+            "wc:{}@2?relay-protocol=irn&symKey={}&projectId={}",
+            self.pairing_topic, self.sym_key, self.project_id
+        );
+        println!("📱 Scan with your wallet to approve the session: {}", uri);
+        uri
+    }
+
+    /// Block until the wallet approves the pairing and returns its eip155
+    /// accounts, or until `timeout` elapses. A no-op if a session is already
+    /// active (e.g. restored via [`from_session_file`](Self::from_session_file)).
+    pub async fn ensure_session(&self, timeout: Duration) -> Result<()> {
+        if self.session_topic.lock().is_some() {
+            return Ok(());
+        }
+
+        let url = format!("{}?projectId={}", self.relay_url, self.project_id);
+        let (ws, _) = connect_async(&url).await?;
+        let (mut write, mut read) = ws.split();
+
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let proposal = json!({
+            "id": id,
+            "jsonrpc": "2.0",
+            "method": "wc_sessionPropose",
+            "params": {
+                "pairingTopic": self.pairing_topic,
+                "requiredNamespaces": {
+                    "eip155": {
+                        "chains": [format!("eip155:{}", self.chain_id)],
+                        "methods": ["eth_sendTransaction", "personal_sign"],
+                        "events": ["accountsChanged", "chainChanged"],
+                    }
+                }
+            }
+        });
+        write.send(Message::Text(proposal.to_string())).await?;
+
+        let wait_for_settle = async {
+            while let Some(msg) = read.next().await {
+                if let Message::Text(text) = msg? {
+                    if let Ok(value) = serde_json::from_str::<Value>(&text) {
+                        if value["method"] == json!("wc_sessionSettle") {
+                            let params = &value["params"];
+                            let topic = params["topic"]
+                                .as_str()
+                                .ok_or_else(|| anyhow!("session settle carried no topic"))?;
+                            let account = params["namespaces"]["eip155"]["accounts"]
+                                .as_array()
+                                .and_then(|accounts| accounts.first())
+                                .and_then(Value::as_str)
+                                // Accounts are CAIP-10 (`eip155:1:0xabc...`); we
+                                // only care about the address.
+                                .and_then(|caip10| caip10.rsplit(':').next())
+                                .ok_or_else(|| anyhow!("session settle carried no eip155 account"))?;
+
+                            return Ok((topic.to_string(), account.to_string()));
+                        }
+                    }
+                }
+            }
+            Err(anyhow!("relay closed before the wallet approved the session"))
+        };
+
+        let (topic, account) = tokio::time::timeout(timeout, wait_for_settle)
+            .await
+            .map_err(|_| anyhow!("timed out after {:?} waiting for wallet approval -- did you scan the URI?", timeout))??;
+
+        *self.session_topic.lock() = Some(topic);
+        *self.account.lock() = Some(account);
+        self.save_session()?;
+        Ok(())
+    }
+
+    pub fn account(&self) -> Option<String> {
+        self.account.lock().clone()
+    }
+
+    /// Ask the connected wallet to sign an `eth_sign`-prefixed message,
+    /// without broadcasting anything.
+    pub async fn personal_sign(&self, message: &str) -> Result<String> {
+        let topic = self.require_session_topic()?;
+        let account = self.require_account()?;
+        let hex_message = format!("0x{}", hex::encode(message.as_bytes()));
+
+        let result = self
+            .relay_request(&topic, "personal_sign", json!([hex_message, account]))
+            .await?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("wallet returned no signature"))
+    }
+
+    /// Ask the connected wallet to sign and broadcast a transaction, returning
+    /// the broadcast hash in the same shape the OKX path uses so callers can
+    /// swap between the two.
+    pub async fn send_transaction(&self, to: &str, data: &str, value_wei: u128) -> Result<ExecutionResult> {
+        let topic = self.require_session_topic()?;
+        let account = self.require_account()?;
+
+        let tx = json!({
+            "from": account,
+            "to": to,
+            "data": data,
+            "value": format!("0x{:x}", value_wei),
+        });
+
+        let hash = self.relay_request(&topic, "eth_sendTransaction", json!([tx])).await?;
+        let tx_hash = hash.as_str().ok_or_else(|| anyhow!("wallet returned no tx hash"))?;
+
+        Ok(ExecutionResult {
+            tx_hash: tx_hash.to_string(),
+            status: "submitted".to_string(),
+            gas_used: 0,
+            effective_price: 0.0,
+            amount_out: 0.0,
+        })
+    }
+
+    fn require_session_topic(&self) -> Result<String> {
+        self.session_topic
+            .lock()
+            .clone()
+            .ok_or_else(|| anyhow!("no active WalletConnect session; call ensure_session first"))
+    }
+
+    fn require_account(&self) -> Result<String> {
+        self.account
+            .lock()
+            .clone()
+            .ok_or_else(|| anyhow!("no active WalletConnect session; call ensure_session first"))
+    }
+
+    fn save_session(&self) -> Result<()> {
+        let persisted = PersistedSession {
+            pairing_topic: self.pairing_topic.clone(),
+            sym_key: self.sym_key.clone(),
+            session_topic: self.require_session_topic()?,
+            account: self.require_account()?,
+            chain_id: self.chain_id,
+        };
+        crate::snapshot::write_atomic(&self.session_path, &serde_json::to_vec_pretty(&persisted)?)
+    }
+
+    /// Publish a request on `topic` to the relay and wait for the wallet's
+    /// response to the matching id.
+    async fn relay_request(&self, topic: &str, method: &str, params: Value) -> Result<Value> {
+        let url = format!("{}?projectId={}", self.relay_url, self.project_id);
+        let (ws, _) = connect_async(&url).await?;
+        let (mut write, mut read) = ws.split();
+
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let payload = json!({
+            "id": id,
+            "jsonrpc": "2.0",
+            "method": "wc_sessionRequest",
+            "params": {
+                "topic": topic,
+                "chainId": format!("eip155:{}", self.chain_id),
+                "request": { "method": method, "params": params },
+            }
+        });
+
+        write.send(Message::Text(payload.to_string())).await?;
+
+        while let Some(msg) = read.next().await {
+            if let Message::Text(text) = msg? {
+                if let Ok(value) = serde_json::from_str::<Value>(&text) {
+                    if value["id"] == json!(id) {
+                        if let Some(result) = value.get("result") {
+                            return Ok(result.clone());
+                        }
+                        if let Some(error) = value.get("error") {
+                            return Err(anyhow!("wallet rejected request: {error}"));
+                        }
+                    }
+                }
+            }
+        }
+
+        Err(anyhow!("relay closed before wallet responded"))
+    }
+}
+
+#[async_trait]
+impl TradeSigner for WalletConnectSigner {
+    /// Build the Uniswap V2 `swapExactETHForTokens` calldata ourselves (OKX
+    /// builds it server-side for the custodial path; here the wallet needs a
+    /// ready-to-sign transaction) and hand it to the connected wallet over
+    /// the relay.
+    async fn execute_buy_order(&self, params: TradeParams) -> Result<ExecutionResult> {
+        let token: Address = params.token_address.parse()?;
+        let weth: Address = WETH_ADDRESS.parse()?;
+        let to: Address = self.require_account()?.parse()?;
+        let deadline = U256::from(now_secs() + 600);
+        let amount_in = params.amount_in.raw;
+
+        // No on-chain quote of our own to derive amountOutMin from here, so
+        // floor it at the slippage-tolerant fraction of the ETH amount in --
+        // the same bound the risk layer already sized the trade against.
+        let amount_out_min = amount_in
+            * U256::from(((1.0 - params.slippage_tolerance).max(0.0) * 10_000.0) as u64)
+            / U256::from(10_000);
+
+        let encoded_args = encode(&[
+            Token::Uint(amount_out_min),
+            Token::Array(vec![Token::Address(weth), Token::Address(token)]),
+            Token::Address(to),
+            Token::Uint(deadline),
+        ]);
+        let mut calldata = SWAP_EXACT_ETH_FOR_TOKENS_SELECTOR.to_vec();
+        calldata.extend_from_slice(&encoded_args);
+
+        self.send_transaction(
+            UNISWAP_V2_ROUTER,
+            &format!("0x{}", hex::encode(calldata)),
+            amount_in.as_u128(),
+        )
+        .await
+    }
+}
+
+fn session_path_from_env() -> PathBuf {
+    std::env::var("WALLETCONNECT_SESSION_PATH")
+        .unwrap_or_else(|_| "data/walletconnect_session.json".to_string())
+        .into()
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
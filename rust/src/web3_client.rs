@@ -0,0 +1,242 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use ethers::providers::{Http, JsonRpcClient, Middleware, Provider, ProviderError};
+use ethers::types::{Address, U256};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// A `JsonRpcClient` transport that fans a request out to an ordered list of
+/// HTTP endpoints, advancing to the next one whenever the current endpoint
+/// errors. The healthy endpoint is remembered so steady-state traffic keeps
+/// hitting a single provider until it fails.
+pub struct FailoverProvider {
+    transports: Vec<Http>,
+    urls: Vec<String>,
+    raw_client: reqwest::Client,
+    current: AtomicUsize,
+}
+
+impl fmt::Debug for FailoverProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FailoverProvider")
+            .field("endpoints", &self.transports.len())
+            .field("current", &self.current.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl FailoverProvider {
+    /// Build a failover transport from one or more HTTP RPC URLs, tried in the
+    /// order given.
+    pub fn new(urls: &[String]) -> Result<Self, ProviderError> {
+        if urls.is_empty() {
+            return Err(ProviderError::CustomError("no RPC endpoints configured".into()));
+        }
+        let transports = urls
+            .iter()
+            .map(|u| {
+                Url::parse(u)
+                    .map(Http::new)
+                    .map_err(|e| ProviderError::CustomError(format!("bad RPC url {u}: {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            transports,
+            urls: urls.to_vec(),
+            raw_client: reqwest::Client::new(),
+            current: AtomicUsize::new(0),
+        })
+    }
+
+    /// Wrap the failover transport in a `Provider` ready for use with the rest
+    /// of the middleware stack.
+    pub fn provider(urls: &[String]) -> Result<Provider<Self>, ProviderError> {
+        Ok(Provider::new(Self::new(urls)?))
+    }
+
+    /// POST an arbitrary JSON-RPC body (including a batch array, which
+    /// `JsonRpcClient::request` has no way to express) to each endpoint in
+    /// order until one answers, with the same sticky-endpoint behavior as
+    /// [`request`](JsonRpcClient::request). Lets callers that build their own
+    /// request bodies (e.g. `MempoolScanner`'s batched `eth_getTransactionByHash`)
+    /// still benefit from failover instead of going around it with a bare
+    /// `reqwest::Client`.
+    pub async fn post_raw(&self, body: &Value) -> Result<Value, ProviderError> {
+        let start = self.current.load(Ordering::Relaxed);
+        let mut last_err = None;
+
+        for offset in 0..self.urls.len() {
+            let idx = (start + offset) % self.urls.len();
+            match self.raw_client.post(&self.urls[idx]).json(body).send().await {
+                Ok(response) => match response.json::<Value>().await {
+                    Ok(value) => {
+                        self.current.store(idx, Ordering::Relaxed);
+                        return Ok(value);
+                    }
+                    Err(e) => last_err = Some(ProviderError::CustomError(e.to_string())),
+                },
+                Err(e) => last_err = Some(ProviderError::CustomError(e.to_string())),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| ProviderError::CustomError("all RPC endpoints failed".into())))
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for FailoverProvider {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        // Serialize params once so we can replay the identical call against each
+        // endpoint on failover.
+        let params = serde_json::to_value(params)?;
+        let start = self.current.load(Ordering::Relaxed);
+        let mut last_err = None;
+
+        for offset in 0..self.transports.len() {
+            let idx = (start + offset) % self.transports.len();
+            match self.transports[idx].request::<Value, R>(method, params.clone()).await {
+                Ok(result) => {
+                    // Stick to the endpoint that just worked.
+                    self.current.store(idx, Ordering::Relaxed);
+                    return Ok(result);
+                }
+                Err(e) => last_err = Some(ProviderError::JsonRpcClientError(Box::new(e))),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| ProviderError::CustomError("all RPC endpoints failed".into())))
+    }
+}
+
+/// A `JsonRpcClient` transport that queries every configured endpoint and
+/// returns whichever response at least `min_agree` of them returned, instead
+/// of trusting the first one to answer. `FailoverProvider` defends against an
+/// endpoint being *down*; this defends against one being *wrong* (a stale or
+/// malicious node serving a plausible but incorrect result).
+pub struct QuorumProvider {
+    transports: Vec<Http>,
+    /// Minimum number of matching responses required before a result is
+    /// trusted. `1` accepts the most common response even if only one
+    /// endpoint returned it; every endpoint is still queried and awaited.
+    min_agree: usize,
+}
+
+impl fmt::Debug for QuorumProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuorumProvider")
+            .field("endpoints", &self.transports.len())
+            .field("min_agree", &self.min_agree)
+            .finish()
+    }
+}
+
+impl QuorumProvider {
+    /// Build a quorum transport over `urls`, requiring at least `min_agree`
+    /// of them to agree on a response before it's trusted.
+    pub fn new(urls: &[String], min_agree: usize) -> Result<Self, ProviderError> {
+        if urls.is_empty() {
+            return Err(ProviderError::CustomError("no RPC endpoints configured".into()));
+        }
+        let transports = urls
+            .iter()
+            .map(|u| {
+                Url::parse(u)
+                    .map(Http::new)
+                    .map_err(|e| ProviderError::CustomError(format!("bad RPC url {u}: {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { transports, min_agree: min_agree.max(1) })
+    }
+
+    /// Wrap the quorum transport in a `Provider` ready for use with the rest
+    /// of the middleware stack.
+    pub fn provider(urls: &[String], min_agree: usize) -> Result<Provider<Self>, ProviderError> {
+        Ok(Provider::new(Self::new(urls, min_agree)?))
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for QuorumProvider {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params)?;
+        // Compare responses as raw JSON rather than as `R` so we don't need
+        // `R: PartialEq`; only the value that reaches quorum gets decoded into
+        // the caller's type.
+        let responses = futures_util::future::join_all(
+            self.transports.iter().map(|t| t.request::<Value, Value>(method, params.clone())),
+        )
+        .await;
+
+        let mut tally: Vec<(Value, usize)> = Vec::new();
+        let mut last_err = None;
+        for response in responses {
+            match response {
+                Ok(value) => match tally.iter_mut().find(|(seen, _)| *seen == value) {
+                    Some(entry) => entry.1 += 1,
+                    None => tally.push((value, 1)),
+                },
+                Err(e) => last_err = Some(ProviderError::JsonRpcClientError(Box::new(e))),
+            }
+        }
+
+        match tally.into_iter().max_by_key(|(_, count)| *count) {
+            Some((value, count)) if count >= self.min_agree => {
+                serde_json::from_value(value).map_err(ProviderError::from)
+            }
+            _ => Err(last_err.unwrap_or_else(|| {
+                ProviderError::CustomError(format!("no {} endpoints agreed on a response", self.min_agree))
+            })),
+        }
+    }
+}
+
+/// Hands out monotonically increasing nonces for addresses we sign locally,
+/// caching the next value in a `DashMap` so a burst of sends doesn't each
+/// re-query `eth_getTransactionCount` (and potentially race each other into
+/// reusing the same nonce). Reads the chain on first use of an address.
+///
+/// `OnchainExecutor` already gets equivalent behavior from ethers' own
+/// `NonceManagerMiddleware` in its stacked `ExecutorClient`; this type exists
+/// for callers that talk to a provider through the bare `JsonRpcClient` stack
+/// above (`FailoverProvider`/`QuorumProvider`) without pulling in the rest of
+/// that middleware stack.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    next: DashMap<Address, AtomicU64>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self { next: DashMap::new() }
+    }
+
+    /// Return the next nonce to use for `address`, querying `middleware` only
+    /// the first time this address is seen.
+    pub async fn next_nonce<M: Middleware>(&self, middleware: &M, address: Address) -> Result<U256, M::Error> {
+        if let Some(entry) = self.next.get(&address) {
+            return Ok(U256::from(entry.fetch_add(1, Ordering::SeqCst)));
+        }
+
+        let onchain = middleware.get_transaction_count(address, None).await?;
+        // Another first use may have seeded the counter while we queried; draw
+        // from whichever counter landed first so no nonce is handed out twice.
+        let entry = self.next.entry(address).or_insert_with(|| AtomicU64::new(onchain.as_u64()));
+        Ok(U256::from(entry.fetch_add(1, Ordering::SeqCst)))
+    }
+}